@@ -0,0 +1,62 @@
+use std::io::{self, Write};
+
+use crate::{CellIndex, TicTacToe};
+
+/// Moves the cursor up to the first line of the board, so the next frame overwrites the previous
+/// one. The board printed by [`TicTacToe::print_to`] is seven lines high.
+const CURSOR_TO_BOARD: &str = "\x1b[7A";
+
+/// Plays `moves` from the empty board and draws the board to `out` after each of them, starting
+/// with the empty board. Between two frames `frame` is invoked, e.g. to sleep. Each frame is drawn
+/// over the previous one using an ANSI escape sequence to move the cursor back up, so `out` should
+/// be a terminal. Every frame has the same size, so nothing besides the board is cleared. An
+/// illegal move is reported as an error of kind [`io::ErrorKind::InvalidInput`], after the frames
+/// before it have been drawn.
+pub fn animate_to(mut out: impl Write, moves: &[CellIndex], frame: impl Fn()) -> io::Result<()> {
+    let mut board = TicTacToe::new();
+    writeln!(out, "{}", board)?;
+    for &mov in moves {
+        board
+            .try_play_move(mov)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        out.flush()?;
+        frame();
+        write!(out, "{}", CURSOR_TO_BOARD)?;
+        writeln!(out, "{}", board)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn one_frame_per_move() {
+        let frames = Cell::new(0);
+        let mut buf = Vec::new();
+        let moves = [4, 0].map(CellIndex::new);
+
+        animate_to(&mut buf, &moves, || frames.set(frames.get() + 1)).unwrap();
+
+        let output = String::from_utf8(buf).unwrap();
+        let boards: Vec<_> = output.split(CURSOR_TO_BOARD).collect();
+        assert_eq!(frames.get(), 2);
+        assert_eq!(boards.len(), 3);
+        assert_eq!(boards[0], format!("{}\n", TicTacToe::new()));
+        assert_eq!(
+            boards[2],
+            "-------\n|O| | |\n|-----|\n| |X| |\n|-----|\n| | | |\n-------\n"
+        );
+    }
+
+    #[test]
+    fn illegal_move_is_invalid_input() {
+        let moves = [4, 4].map(CellIndex::new);
+        let error = animate_to(io::sink(), &moves, || ()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,59 @@
+//! Support for `bincode`. Boards are encoded as the two bytes of [`TicTacToe::to_bytes`], rather
+//! than the padded internal representation.
+
+use bincode::{
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode, Decode, Encode,
+};
+
+use crate::TicTacToe;
+
+impl Encode for TicTacToe {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.to_bytes().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for TicTacToe {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let bytes = <[u8; 2]>::decode(decoder)?;
+        TicTacToe::from_bytes(bytes)
+            .map_err(|_| DecodeError::Other("Bytes do not describe a legal Tic Tac Toe board."))
+    }
+}
+
+impl_borrow_decode!(TicTacToe);
+
+#[cfg(test)]
+mod test {
+
+    use bincode::config;
+
+    use crate::CellIndex;
+
+    use super::*;
+
+    #[test]
+    fn bincode_round_trip() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(0));
+
+        let bytes = bincode::encode_to_vec(board, config::standard()).unwrap();
+        assert_eq!(bytes, board.to_bytes());
+
+        let (decoded, read): (TicTacToe, _) =
+            bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(decoded, board);
+        assert_eq!(read, 2);
+    }
+
+    #[test]
+    fn bincode_rejects_illegal_board() {
+        let result: Result<(TicTacToe, _), _> =
+            bincode::decode_from_slice(&[0xff, 0xff], config::standard());
+        assert!(result.is_err());
+    }
+}
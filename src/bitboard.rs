@@ -1,4 +1,4 @@
-use crate::{Cell, CellIndex};
+use crate::{Cell, CellIndex, MoveError, Player};
 
 /// Bitboard stones
 ///
@@ -22,6 +22,19 @@ impl Bitboard {
         Bitboard(0)
     }
 
+    /// Bits which encode a cell. All other bits are padding and always zero.
+    pub const CELL_BITS: u32 = 0x0777_0777;
+
+    /// The raw bits, see the type level documentation for their layout.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Board with the raw bits. Does not perform any checks.
+    pub fn from_bits(bits: u32) -> Bitboard {
+        Bitboard(bits)
+    }
+
     /// Mark field at index with a stone for a player. Does not perform any checks.
     pub fn mark_cell(&mut self, index: CellIndex, new_state: Cell) {
         // A bitmask which is one at the cell we want to change.
@@ -33,6 +46,18 @@ impl Bitboard {
         }
     }
 
+    /// Like [`Self::mark_cell`], but refuses to place a stone in a cell already occupied by the
+    /// other player, which would leave the cell owned by both. The board is left untouched in that
+    /// case. Marking a cell as empty, or with the stone it already holds, always succeeds.
+    pub fn try_mark_cell(&mut self, index: CellIndex, new_state: Cell) -> Result<(), MoveError> {
+        let current = self.field(index);
+        if current != Cell::Empty && new_state != Cell::Empty && current != new_state {
+            return Err(MoveError::CellOccupied(index));
+        }
+        self.mark_cell(index, new_state);
+        Ok(())
+    }
+
     pub fn field(self, index: CellIndex) -> Cell {
         let bitmask = 1 << (index.row() * (3 + 1) + index.column());
         if bitmask & self.0 != 0 {
@@ -46,17 +71,46 @@ impl Bitboard {
 
     /// True if one player has 3 stones which are allignend horizontal, diagonal or vertical
     pub fn victory(self) -> bool {
+        self.victory_components() != [0; 4]
+    }
+
+    /// The intermediate masks [`Self::victory`] is based on, in the order horizontal, vertical,
+    /// diagonal and anti diagonal. A bit is set if it is the first of three aligned stones of the
+    /// same player in that direction, e.g. bit 0 in the horizontal mask for the top row of player
+    /// one, or bit 18 in the anti diagonal mask for the anti diagonal of player two.
+    pub fn victory_components(self) -> [u32; 4] {
         let (col, row) = (1, 3 + 1);
-        // horizontal or vertical or diagonal 1 or diagonal 2
-        0 != (self.0 & self.0 >> col & self.0 >> (2 * col))
-            | (self.0 & self.0 >> row & self.0 >> (2 * row))
-            | (self.0 & self.0 >> (col + row) & self.0 >> (2 * (col + row)))
-            | (self.0 & self.0 >> (row - col) & self.0 >> (2 * (row - col)))
+        let aligned = |step: u32| self.0 & self.0 >> step & self.0 >> (2 * step);
+        [
+            aligned(col),
+            aligned(row),
+            aligned(col + row),
+            aligned(row - col),
+        ]
+    }
+
+    /// True if `player` has 3 stones which are aligned horizontal, diagonal or vertical
+    pub fn victory_of(self, player: Player) -> bool {
+        let half = match player {
+            Player::One => self.0 & 0xffff,
+            Player::Two => self.0 & 0xffff_0000,
+        };
+        Bitboard(half).victory()
     }
 
-    pub fn stones(self) -> u8 {
+    pub const fn stones(self) -> u8 {
         self.0.count_ones() as u8
     }
+
+    /// Stones of one player with the padding removed. Bit `i` is set if the player has a stone in
+    /// the cell with index `i`.
+    pub fn mask(self, player: Player) -> u16 {
+        let bits = match player {
+            Player::One => self.0,
+            Player::Two => self.0 >> 16,
+        };
+        ((bits & 0b111) | (bits >> 1 & 0b111_000) | (bits >> 2 & 0b111_000_000)) as u16
+    }
 }
 
 #[cfg(test)]
@@ -73,4 +127,53 @@ mod test {
         board.mark_cell(CellIndex(8), Cell::PlayerTwo);
         assert!(board.victory());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn victory_components_of_each_line() {
+        let line = |cells: [u8; 3], stone| {
+            let mut board = Bitboard::new();
+            for i in cells {
+                board.mark_cell(CellIndex(i), stone);
+            }
+            board.victory_components()
+        };
+        assert_eq!(line([0, 1, 2], Cell::PlayerOne), [1, 0, 0, 0]);
+        assert_eq!(line([6, 7, 8], Cell::PlayerOne), [1 << 8, 0, 0, 0]);
+        assert_eq!(line([1, 4, 7], Cell::PlayerTwo), [0, 1 << 17, 0, 0]);
+        assert_eq!(line([0, 4, 8], Cell::PlayerOne), [0, 0, 1, 0]);
+        assert_eq!(line([2, 4, 6], Cell::PlayerTwo), [0, 0, 0, 1 << 18]);
+        // Stones of different players are never aligned
+        let mut board = Bitboard::new();
+        board.mark_cell(CellIndex(0), Cell::PlayerOne);
+        board.mark_cell(CellIndex(1), Cell::PlayerTwo);
+        board.mark_cell(CellIndex(2), Cell::PlayerOne);
+        assert_eq!(board.victory_components(), [0; 4]);
+    }
+
+    #[test]
+    fn try_mark_cell_refuses_stone_of_other_player() {
+        let mut board = Bitboard::new();
+        board.try_mark_cell(CellIndex(4), Cell::PlayerOne).unwrap();
+        board.try_mark_cell(CellIndex(4), Cell::PlayerOne).unwrap();
+        assert_eq!(
+            board.try_mark_cell(CellIndex(4), Cell::PlayerTwo),
+            Err(MoveError::CellOccupied(CellIndex(4)))
+        );
+        assert_eq!(board.field(CellIndex(4)), Cell::PlayerOne);
+        assert_eq!(board.stones(), 1);
+
+        board.try_mark_cell(CellIndex(4), Cell::Empty).unwrap();
+        board.try_mark_cell(CellIndex(4), Cell::PlayerTwo).unwrap();
+        assert_eq!(board.field(CellIndex(4)), Cell::PlayerTwo);
+    }
+
+    #[test]
+    fn mask_removes_padding() {
+        let mut board = Bitboard::new();
+        board.mark_cell(CellIndex(2), Cell::PlayerOne);
+        board.mark_cell(CellIndex(3), Cell::PlayerOne);
+        board.mark_cell(CellIndex(8), Cell::PlayerTwo);
+        assert_eq!(board.mask(Player::One), 0b000_001_100);
+        assert_eq!(board.mask(Player::Two), 0b100_000_000);
+    }
+}
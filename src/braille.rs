@@ -0,0 +1,65 @@
+use crate::{Cell, CellIndex, TicTacToe};
+
+/// Braille dots in the left column, from top to bottom, excluding the lowest.
+const LEFT_DOTS: [u32; 3] = [0x01, 0x02, 0x04];
+/// Braille dots in the right column, from top to bottom, excluding the lowest.
+const RIGHT_DOTS: [u32; 3] = [0x08, 0x10, 0x20];
+
+impl TicTacToe {
+    /// Renders the board as three Unicode braille characters, one for each column from left to
+    /// right. Within a character the upper three rows of dots correspond to the rows of the board.
+    /// A stone of player one raises the left dot, a stone of player two the right dot, and empty
+    /// cells raise neither. The lowest row of dots is never raised.
+    ///
+    /// ```
+    /// use tic_tac_toe_board::{CellIndex, TicTacToe};
+    ///
+    /// let mut board = TicTacToe::new();
+    /// board.play_move(&CellIndex::new(0));
+    /// board.play_move(&CellIndex::new(4));
+    /// assert_eq!(board.to_braille(), "⠁⠐⠀");
+    /// ```
+    pub fn to_braille(&self) -> String {
+        (0..3)
+            .map(|column| {
+                let dots = (0..3).fold(0, |dots, row| {
+                    match self.0.field(CellIndex(row * 3 + column)) {
+                        Cell::Empty => dots,
+                        Cell::PlayerOne => dots | LEFT_DOTS[row as usize],
+                        Cell::PlayerTwo => dots | RIGHT_DOTS[row as usize],
+                    }
+                });
+                char::from_u32(0x2800 + dots).expect("Braille patterns are valid chars.")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn braille_of_empty_and_full_board() {
+        assert_eq!(TicTacToe::new().to_braille(), "⠀⠀⠀");
+
+        // -------
+        // |X|O|X|
+        // |-----|
+        // |X|O|O|
+        // |-----|
+        // |O|X|X|
+        // -------
+        let mut board = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            board.play_move(&CellIndex::new(i));
+        }
+        // Left column: X X O, middle column: O O X, right column: X O X
+        let expected: String = [0x01 | 0x02 | 0x20, 0x08 | 0x10 | 0x04, 0x01 | 0x10 | 0x04]
+            .map(|dots| char::from_u32(0x2800 + dots).unwrap())
+            .iter()
+            .collect();
+        assert_eq!(board.to_braille(), expected);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{CellIndex, MoveError, TicTacToe};
+
+/// Why a move is good or bad, see [`TicTacToe::explain_move`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum MoveKind {
+    /// The move completes a line and wins the game.
+    WinsImmediately,
+    /// The move occupies a cell in which the opponent could have completed a line.
+    BlocksThreat,
+    /// The move creates threats to complete a line in two different cells. The opponent can only
+    /// block one of them.
+    CreatesFork,
+    /// None of the above, but the move keeps the best outcome achievable under perfect play.
+    PreservesOutcome,
+    /// The move makes the outcome under perfect play worse for the player making it.
+    WorsensOutcome,
+}
+
+/// Human readable rationale of a move, see [`TicTacToe::explain_move`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct MoveExplanation {
+    /// Most important reason for (or against) the move
+    pub kind: MoveKind,
+    /// Value of the position after the move from the perspective of the player making it,
+    /// assuming perfect play. Same scale as [`TicTacToe::evaluate`].
+    pub value: i32,
+}
+
+impl TicTacToe {
+    /// Explains what playing `mov` does for the player to move. If several categories apply, the
+    /// first one in the order of [`MoveKind`] is reported, e.g. a move which wins and blocks at the
+    /// same time wins immediately. Fails if the move is illegal.
+    pub fn explain_move(&self, mov: CellIndex) -> Result<MoveExplanation, MoveError> {
+        let player = self.current_player();
+        let mut child = *self;
+        child.try_play_move(mov)?;
+        let value = -child.evaluate();
+        let kind = if child.0.victory() {
+            MoveKind::WinsImmediately
+        } else if self.completing_cells(player.opponent()) & mov.mask() != 0 {
+            MoveKind::BlocksThreat
+        } else if child.completing_cells(player).count_ones() >= 2 {
+            MoveKind::CreatesFork
+        } else if value == self.evaluate() {
+            MoveKind::PreservesOutcome
+        } else {
+            MoveKind::WorsensOutcome
+        };
+        Ok(MoveExplanation { kind, value })
+    }
+
+    /// The move recommended by [`Self::best_move`] together with its explanation. `None` if the
+    /// game is already finished.
+    pub fn hint(&self) -> Option<(CellIndex, MoveExplanation)> {
+        let mov = self.best_move()?;
+        let explanation = self
+            .explain_move(mov)
+            .expect("Best move must always be legal.");
+        Some((mov, explanation))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn board(moves: &[u8]) -> TicTacToe {
+        let mut board = TicTacToe::new();
+        for &i in moves {
+            board.play_move(&CellIndex::new(i));
+        }
+        board
+    }
+
+    #[test]
+    fn explain_moves() {
+        let explain = |moves: &[u8], mov| board(moves).explain_move(CellIndex::new(mov)).unwrap();
+
+        assert_eq!(
+            explain(&[4, 0, 2, 1], 6),
+            MoveExplanation {
+                kind: MoveKind::WinsImmediately,
+                value: 1
+            }
+        );
+        assert_eq!(explain(&[4, 0, 2], 6).kind, MoveKind::BlocksThreat);
+        // Player one threatens 3 and 4 after taking the corner 6
+        assert_eq!(
+            explain(&[0, 8, 2, 1], 6),
+            MoveExplanation {
+                kind: MoveKind::CreatesFork,
+                value: 1
+            }
+        );
+        assert_eq!(explain(&[], 4).kind, MoveKind::PreservesOutcome);
+        // Answering a corner opening with an edge loses
+        assert_eq!(
+            explain(&[0], 1),
+            MoveExplanation {
+                kind: MoveKind::WorsensOutcome,
+                value: -1
+            }
+        );
+    }
+
+    #[test]
+    fn explain_illegal_move() {
+        assert_eq!(
+            board(&[4]).explain_move(CellIndex::new(4)),
+            Err(MoveError::CellOccupied(CellIndex::new(4)))
+        );
+    }
+
+    #[test]
+    fn hint_for_player_two() {
+        assert_eq!(
+            board(&[4, 0, 2]).hint(),
+            Some((
+                CellIndex::new(6),
+                MoveExplanation {
+                    kind: MoveKind::BlocksThreat,
+                    value: 0
+                }
+            ))
+        );
+        assert_eq!(board(&[4, 0, 2, 1, 6]).hint(), None);
+    }
+}
@@ -0,0 +1,321 @@
+use std::{error::Error, fmt};
+
+use crate::{bitboard::Bitboard, Cell, CellIndex, TicTacToe};
+
+/// Number of distinct assignments of the three cell states to nine cells.
+const NUM_IDS: u16 = 19683;
+
+/// Error returned when decoding a board from its ternary id or its bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// The id is `19683` or larger and therefore does not describe any board.
+    OutOfRange(u16),
+    /// The id describes a position which can not arise in a legal game.
+    IllegalPosition,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::OutOfRange(id) => write!(
+                f,
+                "Board id {} is out of range. Ids must be smaller than {}.",
+                id, NUM_IDS
+            ),
+            DecodeError::IllegalPosition => write!(
+                f,
+                "Board id describes a position which can not arise in a legal game."
+            ),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Error returned by [`TicTacToe::from_bits`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BitsError {
+    /// One of the padding bits is set.
+    Padding,
+    /// A cell is occupied by both players at once.
+    DoubleOccupied(CellIndex),
+    /// The bits describe a position which can not arise in a legal game.
+    IllegalPosition,
+}
+
+impl fmt::Display for BitsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitsError::Padding => write!(f, "Padding bits of the board must be zero."),
+            BitsError::DoubleOccupied(index) => {
+                write!(f, "The {} is occupied by both players.", index)
+            }
+            BitsError::IllegalPosition => write!(
+                f,
+                "Bits describe a position which can not arise in a legal game."
+            ),
+        }
+    }
+}
+
+impl Error for BitsError {}
+
+/// Digits of a share code, see [`TicTacToe::to_share_code`]. Leaves out `i`, `l`, `o` and `u`,
+/// which are easily confused with `1`, `0` or `v`.
+const SHARE_CODE_ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Error returned by [`TicTacToe::from_share_code`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShareCodeError {
+    /// Share codes are exactly three characters long. Holds the number of characters found.
+    WrongLength(usize),
+    /// The character is not part of the alphabet of share codes.
+    InvalidCharacter(char),
+    /// The code is well formed, but its id does not describe a legal position.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShareCodeError::WrongLength(length) => write!(
+                f,
+                "Share codes consist of exactly 3 characters, found {}.",
+                length
+            ),
+            ShareCodeError::InvalidCharacter(c) => {
+                write!(f, "'{}' is not a valid character in a share code.", c)
+            }
+            ShareCodeError::Decode(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ShareCodeError {}
+
+impl TicTacToe {
+    /// The [ternary id](Self::ternary_id) as three digits in base 32, most significant first. The
+    /// digits are `0123456789abcdefghjkmnpqrstvwxyz`, i.e. the decimal digits followed by the
+    /// lowercase letters without `i`, `l`, `o` and `u`. Short and safe to use in URLs, e.g. `"000"`
+    /// for the empty board.
+    pub fn to_share_code(&self) -> String {
+        let id = self.ternary_id() as usize;
+        [id >> 10, id >> 5, id]
+            .iter()
+            .map(|&digit| SHARE_CODE_ALPHABET[digit % 32] as char)
+            .collect()
+    }
+
+    /// Inverse of [`Self::to_share_code`]. Uppercase letters are accepted, too. Fails if the code
+    /// is malformed or does not describe a legal position.
+    pub fn from_share_code(code: &str) -> Result<TicTacToe, ShareCodeError> {
+        let length = code.chars().count();
+        if length != 3 {
+            return Err(ShareCodeError::WrongLength(length));
+        }
+        let mut id = 0u16;
+        for c in code.chars() {
+            let digit = SHARE_CODE_ALPHABET
+                .iter()
+                .position(|&d| d as char == c.to_ascii_lowercase())
+                .ok_or(ShareCodeError::InvalidCharacter(c))?;
+            id = id * 32 + digit as u16;
+        }
+        TicTacToe::from_ternary_id(id).map_err(ShareCodeError::Decode)
+    }
+
+    /// The internal representation of the board. Bits 0 to 2, 4 to 6 and 8 to 10 hold the stones
+    /// of player one in the rows from top to bottom, each row from left to right. The stones of
+    /// player two are held in the same layout, shifted by 16 bits. All other bits are zero.
+    ///
+    /// Prefer [`Self::to_bytes`] for persistence, which is independent of the internal layout.
+    pub fn to_bits(&self) -> u32 {
+        self.0.bits()
+    }
+
+    /// Inverse of [`Self::to_bits`]. Never panics. Fails if a padding bit is set, a cell is
+    /// occupied by both players or the position can not arise in a legal game. Any `bits` for
+    /// which this succeeds are returned unchanged by `to_bits`.
+    pub fn from_bits(bits: u32) -> Result<TicTacToe, BitsError> {
+        if bits & !Bitboard::CELL_BITS != 0 {
+            return Err(BitsError::Padding);
+        }
+        // Place the stones one by one, so a cell claimed by both players is caught.
+        let mut board = TicTacToe::new();
+        for (half, stone) in [
+            (bits & 0xffff, Cell::PlayerOne),
+            (bits >> 16, Cell::PlayerTwo),
+        ] {
+            let stones = Bitboard::from_bits(half);
+            for index in (0..9).map(CellIndex) {
+                if stones.field(index) == Cell::PlayerOne {
+                    board
+                        .0
+                        .try_mark_cell(index, stone)
+                        .map_err(|_| BitsError::DoubleOccupied(index))?;
+                }
+            }
+        }
+        if !board.is_legal_position() {
+            return Err(BitsError::IllegalPosition);
+        }
+        Ok(board)
+    }
+
+    /// Interprets the board as a number in base three. Cell `i` is the `i`-th least significant
+    /// digit, with `0` for an empty cell, `1` for player one and `2` for player two. Therefore the
+    /// id is always smaller than `3^9 = 19683`, and the empty board has id `0`.
+    pub fn ternary_id(&self) -> u16 {
+        (0..9).rev().fold(0, |id, i| {
+            let digit = match self.0.field(CellIndex(i)) {
+                Cell::Empty => 0,
+                Cell::PlayerOne => 1,
+                Cell::PlayerTwo => 2,
+            };
+            id * 3 + digit
+        })
+    }
+
+    /// Inverse of [`Self::ternary_id`]. Fails if the id is out of range or describes an illegal
+    /// position.
+    pub fn from_ternary_id(id: u16) -> Result<TicTacToe, DecodeError> {
+        if id >= NUM_IDS {
+            return Err(DecodeError::OutOfRange(id));
+        }
+        let mut board = TicTacToe::new();
+        let mut rest = id;
+        for i in 0..9 {
+            let cell = match rest % 3 {
+                0 => Cell::Empty,
+                1 => Cell::PlayerOne,
+                _ => Cell::PlayerTwo,
+            };
+            board
+                .0
+                .try_mark_cell(CellIndex(i), cell)
+                .map_err(|_| DecodeError::IllegalPosition)?;
+            rest /= 3;
+        }
+        if !board.is_legal_position() {
+            return Err(DecodeError::IllegalPosition);
+        }
+        Ok(board)
+    }
+
+    /// The [ternary id](Self::ternary_id) as two bytes in big-endian order. This encoding is
+    /// stable and independent of the internal representation of the board.
+    pub fn to_bytes(&self) -> [u8; 2] {
+        self.ternary_id().to_be_bytes()
+    }
+
+    /// Inverse of [`Self::to_bytes`]. Fails if the bytes are out of range or describe an illegal
+    /// position.
+    pub fn from_bytes(bytes: [u8; 2]) -> Result<TicTacToe, DecodeError> {
+        TicTacToe::from_ternary_id(u16::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn reject_invalid_bits() {
+        assert_eq!(TicTacToe::from_bits(1 << 3), Err(BitsError::Padding));
+        assert_eq!(
+            TicTacToe::from_bits(1 << 5 | 1 << 21),
+            Err(BitsError::DoubleOccupied(CellIndex::new(4)))
+        );
+        // Single stone of player two
+        assert_eq!(
+            TicTacToe::from_bits(1 << 16),
+            Err(BitsError::IllegalPosition)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn from_bits_fails_cleanly_or_round_trips(bits: u32) {
+            // Without masking almost all inputs would be rejected for their padding.
+            for bits in [bits, bits & Bitboard::CELL_BITS] {
+                if let Ok(board) = TicTacToe::from_bits(bits) {
+                    prop_assert_eq!(board.to_bits(), bits);
+                }
+            }
+        }
+
+        #[test]
+        fn legal_boards_round_trip_through_bits(moves in vec(0..9u8, 0..9)) {
+            let mut board = TicTacToe::new();
+            for mov in moves {
+                // Ignore illegal moves, so we end up with an arbitrary legal board.
+                let _ = board.try_play_move(CellIndex::new(mov));
+            }
+            prop_assert_eq!(TicTacToe::from_bits(board.to_bits()), Ok(board));
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut board = TicTacToe::new();
+        assert_eq!(board.to_bytes(), [0, 0]);
+        board.play_move(&CellIndex::new(8));
+        board.play_move(&CellIndex::new(0));
+        // 3^8 + 2
+        assert_eq!(board.ternary_id(), 6563);
+        assert_eq!(board.to_bytes(), [0x19, 0xa3]);
+        assert_eq!(TicTacToe::from_bytes(board.to_bytes()), Ok(board));
+    }
+
+    #[test]
+    fn share_code_round_trip() {
+        assert_eq!(TicTacToe::new().to_share_code(), "000");
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(8));
+        board.play_move(&CellIndex::new(0));
+        // 6563 = 6 * 32^2 + 13 * 32 + 3
+        assert_eq!(board.to_share_code(), "6d3");
+        assert_eq!(TicTacToe::from_share_code("6d3"), Ok(board));
+        assert_eq!(TicTacToe::from_share_code("6D3"), Ok(board));
+    }
+
+    #[test]
+    fn reject_invalid_share_codes() {
+        assert_eq!(
+            TicTacToe::from_share_code("6d"),
+            Err(ShareCodeError::WrongLength(2))
+        );
+        assert_eq!(
+            TicTacToe::from_share_code("6u3"),
+            Err(ShareCodeError::InvalidCharacter('u'))
+        );
+        assert_eq!(
+            TicTacToe::from_share_code("zzz"),
+            Err(ShareCodeError::Decode(DecodeError::OutOfRange(32767)))
+        );
+        assert_eq!(
+            TicTacToe::from_share_code("008"),
+            Err(ShareCodeError::Decode(DecodeError::IllegalPosition))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_bytes() {
+        assert_eq!(
+            TicTacToe::from_bytes(19683u16.to_be_bytes()),
+            Err(DecodeError::OutOfRange(19683))
+        );
+        assert_eq!(
+            TicTacToe::from_bytes([0xff, 0xff]),
+            Err(DecodeError::OutOfRange(0xffff))
+        );
+        // Two stones of player two, none of player one
+        assert_eq!(
+            TicTacToe::from_bytes(8u16.to_be_bytes()),
+            Err(DecodeError::IllegalPosition)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{error::Error, fmt};
+
+use crate::{Cell, CellIndex, MoveError, Player, TicTacToe, TicTacToeState, TranscriptError};
+
+/// A game of Tic Tac Toe which remembers the moves played, so they can be taken back. In contrast
+/// to [`TicTacToe`] the state of the game is cached and updated with each move, so calling
+/// [`Game::state`] is cheap.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    board: TicTacToe,
+    state: TicTacToeState,
+    /// Moves played so far, followed by moves which have been taken back and can be redone.
+    moves: Vec<CellIndex>,
+    /// Number of moves currently on the board.
+    ply: usize,
+}
+
+impl Game {
+    /// A new game starting with an empty board
+    pub fn new() -> Game {
+        Game {
+            board: TicTacToe::new(),
+            state: TicTacToeState::TurnPlayerOne,
+            moves: Vec::new(),
+            ply: 0,
+        }
+    }
+
+    /// A game in which `moves` have been played, starting from the empty board. Fails at the first
+    /// illegal move, e.g. a move played after one player has already won. The error holds the
+    /// index of the move within `moves`.
+    pub fn replay(moves: &[CellIndex]) -> Result<Game, TranscriptError> {
+        let mut game = Game::new();
+        for (ply, &mov) in moves.iter().enumerate() {
+            game.try_play_move(mov)
+                .map_err(|error| TranscriptError { ply, error })?;
+        }
+        Ok(game)
+    }
+
+    /// The current position on the board
+    pub fn board(&self) -> TicTacToe {
+        self.board
+    }
+
+    /// The player whose turn it is. For finished games this is the player who would be next to
+    /// move.
+    pub fn current_player(&self) -> Player {
+        self.board.current_player()
+    }
+
+    /// State of the game. Always identical to `self.board().state()`, yet does not recompute it.
+    pub fn state(&self) -> TicTacToeState {
+        self.state
+    }
+
+    /// All moves played so far, in the order they have been played in.
+    pub fn moves(&self) -> &[CellIndex] {
+        &self.moves[..self.ply]
+    }
+
+    /// All recorded moves, including those which have been taken back and could be redone.
+    #[cfg(feature = "serde")]
+    pub(crate) fn recorded_moves(&self) -> &[CellIndex] {
+        &self.moves
+    }
+
+    /// The cell the most recent move has been played in. `None` if no move has been played yet.
+    pub fn last_move(&self) -> Option<CellIndex> {
+        self.ply.checked_sub(1).map(|i| self.moves[i])
+    }
+
+    /// Places a stone for the current player in the specified Cell. Panics if cell is not empty or
+    /// the game is already finished. Discards any moves which could have been redone.
+    pub fn play_move(&mut self, &mov: &CellIndex) {
+        self.apply(mov);
+        self.moves.truncate(self.ply - 1);
+        self.moves.push(mov);
+    }
+
+    /// Places a stone for the current player in the specified Cell. In contrast to
+    /// [`Self::play_move`] the move is validated and the game is left untouched if it is illegal.
+    pub fn try_play_move(&mut self, mov: CellIndex) -> Result<(), MoveError> {
+        if self.state.is_terminal() {
+            return Err(MoveError::GameFinished);
+        }
+        if self.board.0.field(mov) != Cell::Empty {
+            return Err(MoveError::CellOccupied(mov));
+        }
+        self.play_move(&mov);
+        Ok(())
+    }
+
+    /// Takes back the last move. Returns the cell it has been played in and the player whose stone
+    /// has been removed, who is also the player to move afterwards. `None` if no move has been
+    /// played yet. The move can be played again using [`Game::redo`].
+    pub fn undo(&mut self) -> Option<(CellIndex, Player)> {
+        let mov = self.last_move()?;
+        // Any position a move has been played from is still ongoing, so it must be the turn of
+        // the player whose stone we remove.
+        let (player, state) = match self.board.0.field(mov) {
+            Cell::PlayerOne => (Player::One, TicTacToeState::TurnPlayerOne),
+            _ => (Player::Two, TicTacToeState::TurnPlayerTwo),
+        };
+        self.state = state;
+        self.board.0.mark_cell(mov, Cell::Empty);
+        self.ply -= 1;
+        Some((mov, player))
+    }
+
+    /// Plays the last move taken back by [`Game::undo`] again and returns the cell it has been
+    /// played in. `None` if there is no move to redo.
+    pub fn redo(&mut self) -> Option<CellIndex> {
+        let mov = *self.moves.get(self.ply)?;
+        self.apply(mov);
+        Some(mov)
+    }
+
+    /// Restores the position after the first `ply` moves of the recorded game. Moves after `ply`
+    /// are kept, so they can be reached again with [`Game::redo`] or another call to `goto_ply`.
+    /// Fails if fewer than `ply` moves have been recorded.
+    pub fn goto_ply(&mut self, ply: usize) -> Result<(), PlyOutOfRange> {
+        if ply > self.moves.len() {
+            return Err(PlyOutOfRange {
+                ply,
+                recorded: self.moves.len(),
+            });
+        }
+        while self.ply > ply {
+            self.undo();
+        }
+        while self.ply < ply {
+            self.redo();
+        }
+        Ok(())
+    }
+
+    /// Places the stone on the board and updates the cached state. Does not touch the move list.
+    fn apply(&mut self, mov: CellIndex) {
+        let (stone, victory) = match self.state {
+            TicTacToeState::TurnPlayerOne => (Cell::PlayerOne, TicTacToeState::VictoryPlayerOne),
+            TicTacToeState::TurnPlayerTwo => (Cell::PlayerTwo, TicTacToeState::VictoryPlayerTwo),
+            _ => panic!("Tic Tac Toe game is already finished."),
+        };
+        assert!(self.board.0.field(mov) == Cell::Empty);
+        self.board.0.mark_cell(mov, stone);
+        self.ply += 1;
+        self.state = if self.board.0.victory() {
+            victory
+        } else if !self.board.has_legal_moves() {
+            TicTacToeState::Draw
+        } else if stone == Cell::PlayerOne {
+            TicTacToeState::TurnPlayerTwo
+        } else {
+            TicTacToeState::TurnPlayerOne
+        };
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game::new()
+    }
+}
+
+/// Returned by [`Game::goto_ply`] if the requested ply exceeds the number of recorded moves.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PlyOutOfRange {
+    /// The ply which has been requested
+    pub ply: usize,
+    /// Number of moves recorded in the game
+    pub recorded: usize,
+}
+
+impl fmt::Display for PlyOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Ply {} is out of range. Only {} moves have been recorded.",
+            self.ply, self.recorded
+        )
+    }
+}
+
+impl Error for PlyOutOfRange {}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    /// Small deterministic pseudo random number generator, so tests are reproducible.
+    fn xorshift(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    #[test]
+    fn cached_state_matches_board_for_random_moves_undos_and_redos() {
+        let mut seed = 42;
+        let mut game = Game::new();
+        for _ in 0..10_000 {
+            let open: Vec<_> = game.board().open_fields().collect();
+            let dice = xorshift(&mut seed) % 4;
+            if game.state().is_terminal() || dice == 0 {
+                game.undo();
+            } else if dice == 1 {
+                game.redo();
+            } else {
+                let mov = open[xorshift(&mut seed) as usize % open.len()];
+                game.play_move(&mov);
+            }
+            assert_eq!(game.state(), game.board().state());
+        }
+    }
+
+    #[test]
+    fn draw_once_no_legal_move_is_left() {
+        let mut game = Game::new();
+        for i in [0, 1, 2, 4, 3, 5, 7, 6] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(game.state(), TicTacToeState::TurnPlayerOne);
+        game.play_move(&CellIndex::new(8));
+        assert_eq!(game.state(), TicTacToeState::Draw);
+
+        // Completing a line with the last legal move is a victory, not a draw
+        let mut game = Game::new();
+        for i in [0, 1, 2, 3, 4, 5, 7, 6, 8] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(game.state(), TicTacToeState::VictoryPlayerOne);
+        assert_eq!(game.state(), game.board().state());
+    }
+
+    #[test]
+    fn undo_at_start_of_game() {
+        let mut game = Game::new();
+        assert_eq!(game.undo(), None);
+        assert_eq!(game, Game::new());
+    }
+
+    #[test]
+    fn undo_reports_removed_stone() {
+        let mut game = Game::new();
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(0));
+        assert_eq!(game.undo(), Some((CellIndex::new(0), Player::Two)));
+        assert_eq!(game.current_player(), Player::Two);
+        assert_eq!(game.undo(), Some((CellIndex::new(4), Player::One)));
+        assert_eq!(game.current_player(), Player::One);
+        assert_eq!(game.undo(), None);
+    }
+
+    #[test]
+    fn last_move_through_undo_and_redo() {
+        let mut game = Game::new();
+        assert_eq!(game.last_move(), None);
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(0));
+        assert_eq!(game.last_move(), Some(CellIndex::new(0)));
+        game.undo();
+        assert_eq!(game.last_move(), Some(CellIndex::new(4)));
+        game.redo();
+        assert_eq!(game.last_move(), Some(CellIndex::new(0)));
+        game.undo();
+        game.undo();
+        assert_eq!(game.last_move(), None);
+        assert_eq!(game.redo(), Some(CellIndex::new(4)));
+        // Playing a new move discards the moves which could have been redone
+        game.play_move(&CellIndex::new(8));
+        assert_eq!(game.redo(), None);
+        assert_eq!(game.moves(), &[CellIndex::new(4), CellIndex::new(8)]);
+    }
+
+    #[test]
+    fn replay_transcript() {
+        let moves = [4, 0, 8].map(CellIndex::new);
+        let game = Game::replay(&moves).unwrap();
+        assert_eq!(game.moves(), &moves);
+        assert_eq!(game.state(), TicTacToeState::TurnPlayerTwo);
+    }
+
+    #[test]
+    fn replay_rejects_moves_after_victory() {
+        // Player one completes the diagonal with the fifth move, the sixth is spurious
+        let moves = [0, 1, 4, 2, 8, 5].map(CellIndex::new);
+        assert_eq!(
+            Game::replay(&moves),
+            Err(TranscriptError {
+                ply: 5,
+                error: MoveError::GameFinished
+            })
+        );
+        assert!(Game::replay(&moves[..5]).unwrap().state().is_terminal());
+    }
+
+    #[test]
+    fn scrub_through_recorded_game() {
+        let mut game = Game::new();
+        for i in [4, 0, 8, 2] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let end = game.clone();
+
+        game.goto_ply(1).unwrap();
+        assert_eq!(game.moves(), &[CellIndex::new(4)]);
+        assert_eq!(game.state(), TicTacToeState::TurnPlayerTwo);
+
+        game.goto_ply(4).unwrap();
+        assert_eq!(game, end);
+
+        assert_eq!(
+            game.goto_ply(5),
+            Err(PlyOutOfRange {
+                ply: 5,
+                recorded: 4
+            })
+        );
+        assert_eq!(game, end);
+    }
+}
@@ -0,0 +1,305 @@
+use std::{error::Error, fmt};
+
+use crate::{Cell, CellIndex, Player, TicTacToe};
+
+/// Characters which represent the contents of a cell in a grid parsed by [`TicTacToe::from_grid`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GridConfig {
+    /// Marks a stone of player one
+    pub player_one: char,
+    /// Marks a stone of player two
+    pub player_two: char,
+    /// Marks a cell which is not occupied by either player
+    pub empty: char,
+}
+
+impl Default for GridConfig {
+    /// `X` for player one, `O` for player two and `.` for empty cells.
+    fn default() -> GridConfig {
+        GridConfig {
+            player_one: 'X',
+            player_two: 'O',
+            empty: '.',
+        }
+    }
+}
+
+/// Error returned by [`TicTacToe::from_grid`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GridError {
+    /// The grid did not contain exactly nine cells. Holds the number of cells found.
+    WrongCellCount(usize),
+    /// The cells describe a position which can not be reached by legal play.
+    IllegalPosition,
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::WrongCellCount(count) => {
+                write!(f, "Expected exactly 9 cells in grid, found {}.", count)
+            }
+            GridError::IllegalPosition => {
+                write!(
+                    f,
+                    "Grid describes a position which can not arise in a legal game."
+                )
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Error returned by [`TicTacToe::from_cells_with_turn`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SetupError {
+    /// Player one must have as many stones as player two, or exactly one more.
+    StoneCounts {
+        /// Number of stones of player one
+        player_one: u8,
+        /// Number of stones of player two
+        player_two: u8,
+    },
+    /// According to the stone counts it is the turn of the other player. Holds the player who is
+    /// to move.
+    WrongTurn(Player),
+    /// A player has completed a line, yet the opponent placed a stone afterwards, or both players
+    /// have completed a line.
+    WinContradictsTurn,
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetupError::StoneCounts {
+                player_one,
+                player_two,
+            } => write!(
+                f,
+                "Player one has {} stones and player two has {}. Player one must have as many \
+                stones as player two, or one more.",
+                player_one, player_two
+            ),
+            SetupError::WrongTurn(player) => write!(
+                f,
+                "According to the number of stones it is the turn of player {}.",
+                match player {
+                    Player::One => "one",
+                    Player::Two => "two",
+                }
+            ),
+            SetupError::WinContradictsTurn => write!(
+                f,
+                "A stone has been placed after the game has already been won."
+            ),
+        }
+    }
+}
+
+impl Error for SetupError {}
+
+impl TicTacToe {
+    /// Sets up a position from the contents of its cells, ordered by index, and the player to
+    /// move. Since players alternate starting with player one, the stones already determine whose
+    /// turn it is, so `to_move` is only validated and not stored. This is useful to catch mistakes
+    /// in hand written positions. Fails if the stone counts are impossible, `to_move` is not the
+    /// player the stones imply, or a player has won yet the opponent moved afterwards. For
+    /// finished games `to_move` is the player who would be next, i.e. the loser.
+    pub fn from_cells_with_turn(
+        cells: [Cell; 9],
+        to_move: Player,
+    ) -> Result<TicTacToe, SetupError> {
+        let mut board = TicTacToe::new();
+        for (index, cell) in cells.into_iter().enumerate() {
+            board.0.mark_cell(CellIndex(index as u8), cell);
+        }
+        let player_one = board.player_mask(Player::One).count_ones() as u8;
+        let player_two = board.player_mask(Player::Two).count_ones() as u8;
+        if player_one != player_two && player_one != player_two + 1 {
+            return Err(SetupError::StoneCounts {
+                player_one,
+                player_two,
+            });
+        }
+        if board.current_player() != to_move {
+            return Err(SetupError::WrongTurn(board.current_player()));
+        }
+        if !board.is_legal_position() {
+            return Err(SetupError::WinContradictsTurn);
+        }
+        Ok(board)
+    }
+
+    /// Nine characters, one per cell from top left to bottom right, using the symbols of
+    /// [`GridConfig::default`], e.g. `"X...O...."`. Can be parsed again with [`Self::from_grid`].
+    pub fn to_compact(&self) -> String {
+        let mut compact = String::with_capacity(9);
+        // Writing to a `String` never fails.
+        let _ = self.write_compact(&mut compact);
+        compact
+    }
+
+    /// Writes the same characters as [`Self::to_compact`] to `out`, without allocating.
+    pub fn write_compact(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let config = GridConfig::default();
+        for i in 0..9 {
+            out.write_char(match self.0.field(CellIndex(i)) {
+                Cell::Empty => config.empty,
+                Cell::PlayerOne => config.player_one,
+                Cell::PlayerTwo => config.player_two,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Parses a board from a grid of characters. Only characters specified in `config` are
+    /// considered cells. They are read from left to right and top to bottom. Any other character,
+    /// e.g. whitespace, line breaks or separators like `/` and `|` is ignored.
+    ///
+    /// ```
+    /// use tic_tac_toe_board::{GridConfig, TicTacToe};
+    ///
+    /// let board = TicTacToe::from_grid("X.O / .X. / O..", GridConfig::default()).unwrap();
+    /// assert_eq!(board.open_fields().count(), 5);
+    /// ```
+    pub fn from_grid(grid: &str, config: GridConfig) -> Result<TicTacToe, GridError> {
+        let mut board = TicTacToe::new();
+        let mut count = 0;
+        for c in grid.chars() {
+            let cell = if c == config.player_one {
+                Cell::PlayerOne
+            } else if c == config.player_two {
+                Cell::PlayerTwo
+            } else if c == config.empty {
+                Cell::Empty
+            } else {
+                continue;
+            };
+            if count < 9 {
+                board
+                    .0
+                    .try_mark_cell(CellIndex(count as u8), cell)
+                    .map_err(|_| GridError::IllegalPosition)?;
+            }
+            count += 1;
+        }
+        if count != 9 {
+            return Err(GridError::WrongCellCount(count));
+        }
+        if !board.is_legal_position() {
+            return Err(GridError::IllegalPosition);
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn parse_grid_with_custom_symbols() {
+        let config = GridConfig {
+            player_one: 'c',
+            player_two: 'd',
+            empty: '_',
+        };
+        let board = TicTacToe::from_grid("c _ d\n_ c _\nd _ _", config).unwrap();
+
+        let mut expected = TicTacToe::new();
+        expected.play_move(&CellIndex::new(0));
+        expected.play_move(&CellIndex::new(2));
+        expected.play_move(&CellIndex::new(4));
+        expected.play_move(&CellIndex::new(6));
+        assert_eq!(board, expected);
+    }
+
+    /// Writes into a fixed buffer on the stack, failing once it is full.
+    struct StackBuffer {
+        bytes: [u8; 9],
+        len: usize,
+    }
+
+    impl fmt::Write for StackBuffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn compact_form_without_allocation() {
+        let board = TicTacToe::from_openings(&[0, 4].map(CellIndex::new)).unwrap();
+        let mut buffer = StackBuffer {
+            bytes: [0; 9],
+            len: 0,
+        };
+        board.write_compact(&mut buffer).unwrap();
+
+        assert_eq!(&buffer.bytes, b"X...O....");
+        assert_eq!(buffer.bytes, board.to_compact().as_bytes());
+        assert_eq!(
+            TicTacToe::from_grid(&board.to_compact(), GridConfig::default()),
+            Ok(board)
+        );
+    }
+
+    #[test]
+    fn reject_grid_with_wrong_cell_count() {
+        let config = GridConfig::default();
+        assert_eq!(
+            TicTacToe::from_grid("X.O/.X./O.", config),
+            Err(GridError::WrongCellCount(8))
+        );
+        assert_eq!(
+            TicTacToe::from_grid("X.O/.X./O...", config),
+            Err(GridError::WrongCellCount(10))
+        );
+    }
+
+    #[test]
+    fn setup_position_with_turn() {
+        use Cell::{Empty as E, PlayerOne as X, PlayerTwo as O};
+
+        let cells = [X, E, E, E, O, E, E, E, E];
+        let board = TicTacToe::from_cells_with_turn(cells, Player::One).unwrap();
+        assert_eq!(board.current_player(), Player::One);
+        assert_eq!(
+            TicTacToe::from_cells_with_turn(cells, Player::Two),
+            Err(SetupError::WrongTurn(Player::One))
+        );
+
+        let cells = [X, X, E, E, E, E, E, E, E];
+        assert_eq!(
+            TicTacToe::from_cells_with_turn(cells, Player::Two),
+            Err(SetupError::StoneCounts {
+                player_one: 2,
+                player_two: 0
+            })
+        );
+
+        // Player one completed the top row, yet player two moved afterwards
+        let cells = [X, X, X, O, O, E, O, E, E];
+        assert_eq!(
+            TicTacToe::from_cells_with_turn(cells, Player::One),
+            Err(SetupError::WinContradictsTurn)
+        );
+    }
+
+    #[test]
+    fn reject_illegal_grid() {
+        let config = GridConfig::default();
+        // Player one has two more stones than player two
+        assert_eq!(
+            TicTacToe::from_grid("XX./.X./O..", config),
+            Err(GridError::IllegalPosition)
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use crate::{CellIndex, MoveError, Player, TicTacToe, TicTacToeState, LINE_MASKS};
+
+/// Weights of the positional features combined by [`TicTacToe::heuristic_score`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HeuristicWeights {
+    /// Awarded for a stone in the center
+    pub center: i32,
+    /// Awarded for each stone in a corner
+    pub corner: i32,
+    /// Awarded for each line with two own stones and an empty third cell, see
+    /// [`TicTacToe::open_twos`]
+    pub open_two: i32,
+    /// Awarded to the player to move if they can complete a line with their next move
+    pub immediate_win: i32,
+}
+
+impl Default for HeuristicWeights {
+    /// Prefers the center over corners and open twos over both. The chance to win immediately
+    /// outweighs everything else.
+    fn default() -> Self {
+        HeuristicWeights {
+            center: 3,
+            corner: 2,
+            open_two: 5,
+            immediate_win: 100,
+        }
+    }
+}
+
+/// Magnitude of the score [`TicTacToe::search`] assigns to a won game, before accounting for the
+/// number of empty cells. Large enough to dominate any heuristic score with sensible weights.
+const SEARCH_WIN: i32 = 1_000_000;
+
+/// Cells in the corners of the board, as a mask in the format of [`TicTacToe::player_mask`].
+const CORNERS: u16 = 0b101_000_101;
+/// The center cell, as a mask in the format of [`TicTacToe::player_mask`].
+const CENTER: u16 = 0b000_010_000;
+
+impl TicTacToe {
+    /// Number of lines in which `player` has two stones and the third cell is still empty. Each
+    /// of these lines could be completed with a single move.
+    pub fn open_twos(&self, player: Player) -> u8 {
+        let own = self.player_mask(player);
+        let occupied = self.occupied_mask();
+        LINE_MASKS
+            .iter()
+            .filter(|&&line| (own & line).count_ones() == 2 && (occupied & line).count_ones() == 2)
+            .count() as u8
+    }
+
+    /// Number of lines `player` could still complete, i.e. lines without a stone of the opponent.
+    /// Once this drops to zero, `player` can no longer win. Lines already completed by `player`
+    /// count as well.
+    pub fn line_potential(&self, player: Player) -> u8 {
+        let opponent = self.player_mask(player.opponent());
+        LINE_MASKS
+            .iter()
+            .filter(|&&line| opponent & line == 0)
+            .count() as u8
+    }
+
+    /// Number of lines through `index` which the player to move could still complete, in the sense
+    /// of [`Self::line_potential`]. On the empty board this is four for the center, three for each
+    /// corner and two for each edge.
+    pub fn winnable_lines_through(&self, index: CellIndex) -> u8 {
+        let opponent = self.player_mask(self.current_player().opponent());
+        LINE_MASKS
+            .iter()
+            .filter(|&&line| line & index.mask() != 0 && opponent & line == 0)
+            .count() as u8
+    }
+
+    /// `true` if nobody has won and neither player can complete a line anymore, no matter how the
+    /// remaining cells are filled. The game is bound to end in a draw, even though [`Self::state`]
+    /// only reports it once the board is full. See [`Self::state_with_dead_draws`] to report it
+    /// early.
+    pub fn is_dead_draw(&self) -> bool {
+        !self.0.victory()
+            && self.line_potential(Player::One) == 0
+            && self.line_potential(Player::Two) == 0
+    }
+
+    /// Like [`Self::state`], but reports [`TicTacToeState::Draw`] as soon as the position is a
+    /// dead draw according to [`Self::is_dead_draw`].
+    pub fn state_with_dead_draws(&self) -> TicTacToeState {
+        if self.is_dead_draw() {
+            TicTacToeState::Draw
+        } else {
+            self.state()
+        }
+    }
+
+    /// Number of lines the player to move could complete with their next move after playing `mov`,
+    /// i.e. [`Self::open_twos`] of the resulting position. Two or more indicate a fork. Lines are
+    /// counted even if `mov` already wins the game. Fails if the move is illegal.
+    pub fn threats_after(&self, mov: CellIndex) -> Result<u8, MoveError> {
+        let player = self.current_player();
+        let mut child = *self;
+        child.try_play_move(mov)?;
+        Ok(child.open_twos(player))
+    }
+
+    /// A move chosen by simple rules of thumb, without searching the game tree. In order of
+    /// priority: Complete a line, block a line of the opponent, take the center, take a corner,
+    /// take an edge. Ties are broken by the lowest index. `None` if the game is already finished.
+    pub fn heuristic_move(&self) -> Option<CellIndex> {
+        if self.state().is_terminal() {
+            return None;
+        }
+        let player = self.current_player();
+        let completes =
+            |player: Player, mov: CellIndex| self.completing_cells(player) & mov.mask() != 0;
+        let open = || self.open_fields();
+        open()
+            .find(|&mov| completes(player, mov))
+            .or_else(|| open().find(|&mov| completes(player.opponent(), mov)))
+            .or_else(|| open().find(|&mov| mov.0 == 4))
+            .or_else(|| open().find(|&mov| mov.0 % 2 == 0))
+            .or_else(|| open().next())
+    }
+
+    /// Empty cells which would complete a line for `player`, as a mask in the format of
+    /// [`Self::player_mask`].
+    pub(crate) fn completing_cells(&self, player: Player) -> u16 {
+        let own = self.player_mask(player);
+        let empty = !self.occupied_mask() & 0b111_111_111;
+        LINE_MASKS
+            .iter()
+            .filter(|&&line| (own & line).count_ones() == 2)
+            .fold(0, |cells, &line| cells | (line & empty))
+    }
+
+    /// Cheap estimate of the position from the perspective of the player to move, without searching
+    /// the game tree. Center, corner and open two features count positive for the player to move
+    /// and negative for the opponent, and are weighted according to `weights`. In contrast to
+    /// [`Self::evaluate`] the score is not exact and does not detect finished games.
+    pub fn heuristic_score(&self, weights: &HeuristicWeights) -> i32 {
+        let player = self.current_player();
+        let features = |player: Player| {
+            let stones = self.player_mask(player);
+            weights.center * (stones & CENTER).count_ones() as i32
+                + weights.corner * (stones & CORNERS).count_ones() as i32
+                + weights.open_two * i32::from(self.open_twos(player))
+        };
+        let immediate_win = if self.completing_cells(player) != 0 {
+            weights.immediate_win
+        } else {
+            0
+        };
+        features(player) - features(player.opponent()) + immediate_win
+    }
+
+    /// Best move for the player to move according to a minimax search limited to `depth` plies,
+    /// together with its score from the perspective of the player to move. Positions at the horizon
+    /// are scored with [`Self::heuristic_score`]. Won games are scored beyond any heuristic score,
+    /// preferring faster wins and slower losses, draws score zero. With a depth of at least
+    /// [`Self::plies_remaining`] the heuristic is never consulted and the move agrees with perfect
+    /// play, see [`Self::best_moves`]. At least one ply is searched, even if `depth` is zero. Ties
+    /// are broken by the lowest index. `None` if the game is already finished.
+    pub fn search(&self, depth: u8, weights: &HeuristicWeights) -> Option<(CellIndex, i32)> {
+        if self.state().is_terminal() {
+            return None;
+        }
+        let mut best = None;
+        let mut alpha = -i32::MAX;
+        for mov in self.open_fields() {
+            let mut child = *self;
+            child.play_move(&mov);
+            let value = -alpha_beta(child, depth.saturating_sub(1), -i32::MAX, -alpha, weights);
+            if best.is_none() || value > alpha {
+                best = Some(mov);
+                alpha = value;
+            }
+        }
+        best.map(|mov| (mov, alpha))
+    }
+
+    /// Every empty cell which would complete a line for either player, no matter whose turn it
+    /// is. Threats of player one come first, each player's threats ordered by ascending cell index.
+    pub fn threats(&self) -> Vec<(Player, CellIndex)> {
+        [Player::One, Player::Two]
+            .into_iter()
+            .flat_map(|player| {
+                let cells = self.completing_cells(player);
+                (0..9)
+                    .filter(move |i| cells & (1 << i) != 0)
+                    .map(move |i| (player, CellIndex(i)))
+            })
+            .collect()
+    }
+
+    /// Open twos of the player to move minus the open twos of the opponent. Always from the
+    /// perspective of the side to move, i.e. positive values mean the player to move has more
+    /// immediate threats than the opponent.
+    pub fn threat_balance(&self) -> i8 {
+        let player = self.current_player();
+        self.open_twos(player) as i8 - self.open_twos(player.opponent()) as i8
+    }
+}
+
+/// Negamax with alpha beta pruning, scoring positions at the horizon heuristically. Same scale as
+/// [`TicTacToe::search`].
+fn alpha_beta(
+    board: TicTacToe,
+    depth: u8,
+    mut alpha: i32,
+    beta: i32,
+    weights: &HeuristicWeights,
+) -> i32 {
+    if board.0.victory() {
+        return -(SEARCH_WIN + i32::from(board.plies_remaining()));
+    }
+    if board.plies_remaining() == 0 {
+        return 0;
+    }
+    if depth == 0 {
+        return board.heuristic_score(weights);
+    }
+    let mut best = -i32::MAX;
+    for mov in board.open_fields() {
+        let mut child = board;
+        child.play_move(&mov);
+        best = best.max(-alpha_beta(child, depth - 1, -beta, -alpha, weights));
+        alpha = alpha.max(best);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{all_legal_positions_canonical, CellIndex, GridConfig};
+
+    use super::*;
+
+    #[test]
+    fn heuristic_move_priorities() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.heuristic_move(), Some(CellIndex::new(4)));
+        game.play_move(&CellIndex::new(4));
+        assert_eq!(game.heuristic_move(), Some(CellIndex::new(0)));
+        game.play_move(&CellIndex::new(0));
+        game.play_move(&CellIndex::new(2));
+        // Block the anti diagonal
+        assert_eq!(game.heuristic_move(), Some(CellIndex::new(6)));
+        game.play_move(&CellIndex::new(1));
+        // Win rather than block the top row
+        assert_eq!(game.heuristic_move(), Some(CellIndex::new(6)));
+    }
+
+    #[test]
+    fn weighted_heuristic_score() {
+        let weights = HeuristicWeights::default();
+        assert_eq!(TicTacToe::new().heuristic_score(&weights), 0);
+
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(4));
+        // Player two to move, player one holds the center
+        assert_eq!(game.heuristic_score(&weights), -3);
+        game.play_move(&CellIndex::new(0));
+        game.play_move(&CellIndex::new(8));
+        // Player two has a corner, player one center and corner
+        assert_eq!(game.heuristic_score(&weights), -3);
+
+        let corners_only = HeuristicWeights {
+            center: 0,
+            corner: 1,
+            open_two: 0,
+            immediate_win: 0,
+        };
+        game.play_move(&CellIndex::new(2));
+        // Player one to move, with one corner against two corners and an open two of player two
+        assert_eq!(game.heuristic_score(&corners_only), -1);
+        assert_eq!(game.heuristic_score(&weights), (3 + 2) - (2 * 2 + 5));
+        game.play_move(&CellIndex::new(5));
+        // Player one fails to block, so player two can win on 1. Player one now has an open two
+        // in the middle row.
+        assert_eq!(
+            game.heuristic_score(&weights),
+            (2 * 2 + 5 + 100) - (3 + 2 + 5)
+        );
+    }
+
+    #[test]
+    fn full_depth_search_agrees_with_perfect_play() {
+        let weights = HeuristicWeights::default();
+        for board in all_legal_positions_canonical().filter(|board| !board.state().is_terminal()) {
+            let (mov, value) = board.search(9, &weights).unwrap();
+            assert!(board.best_moves().contains(&mov));
+            assert_eq!(value.signum(), board.evaluate());
+        }
+    }
+
+    #[test]
+    fn shallow_search_uses_heuristic() {
+        let mut game = TicTacToe::new();
+        // Only the center is worth more than a corner at the horizon of a single ply
+        assert_eq!(
+            game.search(0, &HeuristicWeights::default()),
+            Some((CellIndex::new(4), 3))
+        );
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(0));
+        game.play_move(&CellIndex::new(1));
+        // Player two must block 7, even though the heuristic would prefer a corner
+        let (mov, _) = game.search(2, &HeuristicWeights::default()).unwrap();
+        assert_eq!(mov, CellIndex::new(7));
+        game.play_move(&CellIndex::new(7));
+        game.play_move(&CellIndex::new(2));
+        game.play_move(&CellIndex::new(6));
+        game.play_move(&CellIndex::new(3));
+        game.play_move(&CellIndex::new(5));
+        game.play_move(&CellIndex::new(8));
+        // The board is full, so there is nothing left to search
+        assert_eq!(game.search(9, &HeuristicWeights::default()), None);
+    }
+
+    #[test]
+    fn potential_of_lines() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.line_potential(Player::One), 8);
+        game.play_move(&CellIndex::new(4));
+        // The center blocks four lines for player two
+        assert_eq!(game.line_potential(Player::Two), 4);
+        game.play_move(&CellIndex::new(0));
+        // The corner blocks the top row, the left column and the diagonal
+        assert_eq!(game.line_potential(Player::One), 5);
+    }
+
+    #[test]
+    fn winnable_lines_through_cells() {
+        let empty = TicTacToe::new();
+        let counts =
+            [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| empty.winnable_lines_through(CellIndex(i)));
+        assert_eq!(counts, [3, 2, 3, 2, 4, 2, 3, 2, 3]);
+
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(4));
+        // Player two can only still use the top row and left column through the corner
+        assert_eq!(game.winnable_lines_through(CellIndex::new(0)), 2);
+        assert_eq!(game.winnable_lines_through(CellIndex::new(4)), 0);
+    }
+
+    #[test]
+    fn detect_dead_draws() {
+        let config = GridConfig::default();
+        assert!(!TicTacToe::new().is_dead_draw());
+
+        // Every line through the last empty cell holds stones of both players
+        let board = TicTacToe::from_grid("XOX/XOO/OX.", config).unwrap();
+        assert!(board.is_dead_draw());
+        assert_eq!(board.state(), TicTacToeState::TurnPlayerOne);
+        assert_eq!(board.state_with_dead_draws(), TicTacToeState::Draw);
+
+        // Two cells left, and player one could still complete the right column
+        let board = TicTacToe::from_grid("XOX/XO./OX.", config).unwrap();
+        assert!(!board.is_dead_draw());
+
+        // Won positions are never dead draws
+        let board = TicTacToe::from_grid("XXX/OO./...", config).unwrap();
+        assert!(!board.is_dead_draw());
+        assert_eq!(
+            board.state_with_dead_draws(),
+            TicTacToeState::VictoryPlayerOne
+        );
+    }
+
+    #[test]
+    fn count_threats_after_move() {
+        let mut game = TicTacToe::new();
+        for i in [0, 8, 2, 1] {
+            game.play_move(&CellIndex::new(i));
+        }
+        // Taking the corner 6 threatens the left column and the anti diagonal
+        assert_eq!(game.threats_after(CellIndex::new(6)), Ok(2));
+        assert_eq!(game.threats_after(CellIndex::new(5)), Ok(0));
+        assert_eq!(
+            game.threats_after(CellIndex::new(8)),
+            Err(MoveError::CellOccupied(CellIndex::new(8)))
+        );
+    }
+
+    #[test]
+    fn threats_of_both_players() {
+        let mut game = TicTacToe::new();
+        for i in [0, 3, 4, 5, 2] {
+            game.play_move(&CellIndex::new(i));
+        }
+        // -------
+        // |X| |X|
+        // |-----|
+        // |O|X|O|
+        // |-----|
+        // | | | |
+        // -------
+        let expected = vec![
+            (Player::One, CellIndex::new(1)),
+            (Player::One, CellIndex::new(6)),
+            (Player::One, CellIndex::new(8)),
+        ];
+        assert_eq!(game.threats(), expected);
+
+        let mut game = TicTacToe::new();
+        for i in [0, 4, 1, 3] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let expected = vec![
+            (Player::One, CellIndex::new(2)),
+            (Player::Two, CellIndex::new(5)),
+        ];
+        assert_eq!(game.threats(), expected);
+    }
+
+    #[test]
+    fn threat_balance_from_side_to_move() {
+        // -------
+        // |O| |X|
+        // |-----|
+        // | |X| |
+        // |-----|
+        // | | | |
+        // -------
+        let mut game = TicTacToe::new();
+        for i in [4, 0, 2] {
+            game.play_move(&CellIndex::new(i));
+        }
+
+        // Player one threatens to complete the anti diagonal with 6.
+        assert_eq!(game.open_twos(Player::One), 1);
+        assert_eq!(game.open_twos(Player::Two), 0);
+        // Player two is to move
+        assert_eq!(game.threat_balance(), -1);
+
+        // Player two ignores the threat, now it is player one's turn.
+        game.play_move(&CellIndex::new(1));
+        assert_eq!(game.threat_balance(), 1);
+    }
+}
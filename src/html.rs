@@ -0,0 +1,68 @@
+use std::fmt::Write;
+
+use crate::{Cell, CellIndex, TicTacToe};
+
+impl TicTacToe {
+    /// Renders the board as an HTML `<table>`. Each cell is a `<td>` with the class `cell-x`,
+    /// `cell-o` or `cell-empty` and its index in a `data-index` attribute. Occupied cells contain
+    /// the text `X` or `O`, empty cells contain nothing.
+    ///
+    /// ```
+    /// use tic_tac_toe_board::{CellIndex, TicTacToe};
+    ///
+    /// let mut board = TicTacToe::new();
+    /// board.play_move(&CellIndex::new(0));
+    /// let html = board.to_html();
+    /// assert!(html.starts_with(
+    ///     "<table class=\"tic-tac-toe\"><tr><td class=\"cell-x\" data-index=\"0\">X</td>"
+    /// ));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table class=\"tic-tac-toe\">");
+        for row in 0..3 {
+            html.push_str("<tr>");
+            for index in (row * 3..row * 3 + 3).map(CellIndex) {
+                let (class, content) = match self.0.field(index) {
+                    Cell::Empty => ("cell-empty", ""),
+                    Cell::PlayerOne => ("cell-x", "X"),
+                    Cell::PlayerTwo => ("cell-o", "O"),
+                };
+                // Writing to a `String` never fails.
+                let _ = write!(
+                    html,
+                    "<td class=\"{class}\" data-index=\"{}\">{content}</td>",
+                    index.0
+                );
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        html
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn html_of_board_with_two_stones() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(6));
+
+        let expected = "<table class=\"tic-tac-toe\">\
+            <tr><td class=\"cell-empty\" data-index=\"0\"></td>\
+            <td class=\"cell-empty\" data-index=\"1\"></td>\
+            <td class=\"cell-empty\" data-index=\"2\"></td></tr>\
+            <tr><td class=\"cell-empty\" data-index=\"3\"></td>\
+            <td class=\"cell-x\" data-index=\"4\">X</td>\
+            <td class=\"cell-empty\" data-index=\"5\"></td></tr>\
+            <tr><td class=\"cell-o\" data-index=\"6\">O</td>\
+            <td class=\"cell-empty\" data-index=\"7\"></td>\
+            <td class=\"cell-empty\" data-index=\"8\"></td></tr>\
+            </table>";
+        assert_eq!(board.to_html(), expected);
+    }
+}
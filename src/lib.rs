@@ -1,19 +1,161 @@
+mod animation;
+#[cfg(feature = "bincode")]
+mod binary;
 mod bitboard;
+mod braille;
+mod coach;
+mod encoding;
+mod game;
+mod grid;
+mod heuristics;
+mod html;
+mod markdown;
+mod positions;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "serde")]
+mod serialization;
+mod solver;
+mod strategy;
+mod svg;
+mod symmetry;
+mod tablebase;
+mod transcript;
+
+pub use self::{
+    animation::animate_to,
+    coach::{MoveExplanation, MoveKind},
+    encoding::{BitsError, DecodeError, ShareCodeError},
+    game::{Game, PlyOutOfRange},
+    grid::{GridConfig, GridError, SetupError},
+    heuristics::HeuristicWeights,
+    positions::{
+        all_game_lines, all_legal_positions, all_legal_positions_canonical, boards_of_ply,
+        count_distinct_positions, count_distinct_positions_canonical, distinct_games_canonical,
+        distinct_games_canonical_iter, partition_legal, terminal_positions, OutcomeCounts,
+    },
+    solver::{distinct_opening_outcomes, first_move_value_map, opening_outcomes, Analysis},
+    strategy::{
+        play_game, run_tournament, HeuristicStrategy, MinimaxStrategy, Strategy, TournamentResult,
+    },
+    svg::SvgConfig,
+    symmetry::{group_by_symmetry, Symmetry, SYMMETRIES},
+    tablebase::{export_tablebase, lookup_in},
+    transcript::{is_valid_transcript, validate_transcript, TranscriptError},
+};
+
+#[cfg(feature = "rand")]
+pub use self::{
+    random::{generate_selfplay, outcome_summary, random_game_stats, GameStats, OutcomeSummary},
+    strategy::RandomStrategy,
+};
 
 use bitboard::Bitboard;
-use std::{fmt, io};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+};
 
 /// A TacTacToe board
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub struct TicTacToe(Bitboard);
 
+/// Hashes the occupancy of both players with the padding of the internal representation removed.
+/// Equal positions therefore always hash equally, independent of how they have been constructed.
+impl Hash for TicTacToe {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.planes().hash(state)
+    }
+}
+
 impl TicTacToe {
     pub fn new() -> TicTacToe {
         TicTacToe(Bitboard::new())
     }
 
     pub fn print_to(self, mut out: impl io::Write) -> io::Result<()> {
-        let f = |i| self.0.field(CellIndex(i));
+        write!(out, "{}", self)
+    }
+
+    /// Same grid as [`Self::print_to`], but uses the symbols in `config` for the cells.
+    pub fn print_with_config_to(
+        self,
+        mut out: impl io::Write,
+        config: &PrintConfig,
+    ) -> io::Result<()> {
+        write!(
+            out,
+            "{}",
+            Themed {
+                board: self,
+                config
+            }
+        )
+    }
+
+    /// Prints a wider grid than [`Self::print_to`], in which each cell is three characters wide.
+    /// If a player has won, the stones of the winning line are put in brackets, e.g. `[X]`. Other
+    /// stones are padded with spaces, so draws and ongoing games are printed without any marks.
+    ///
+    /// ```custom
+    /// -------------
+    /// |[X]| O | O |
+    /// |-----------|
+    /// |   |[X]|   |
+    /// |-----------|
+    /// |   |   |[X]|
+    /// -------------
+    /// ```
+    pub fn print_result_to(self, mut out: impl io::Write) -> io::Result<()> {
+        let f = |i| {
+            let index = CellIndex(i);
+            let c = self.0.field(index);
+            if self.is_winning_cell(index) {
+                format!("[{}]", c)
+            } else {
+                format!(" {} ", c)
+            }
+        };
+
+        write!(
+            out,
+            "-------------\n\
+             |{}|{}|{}|\n\
+             |-----------|\n\
+             |{}|{}|{}|\n\
+             |-----------|\n\
+             |{}|{}|{}|\n\
+             -------------",
+            f(0),
+            f(1),
+            f(2),
+            f(3),
+            f(4),
+            f(5),
+            f(6),
+            f(7),
+            f(8)
+        )
+    }
+
+    /// Writes the same grid as [`Self::print_to`] to a [`fmt::Write`]. Both `print_to` and the
+    /// [`fmt::Display`] implementation are based on this method.
+    pub fn write_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.write_with_config_to(out, &PrintConfig::default())
+    }
+
+    /// Same as [`Self::write_to`], but uses the symbols in `config` for the cells.
+    pub fn write_with_config_to(
+        &self,
+        out: &mut impl fmt::Write,
+        config: &PrintConfig,
+    ) -> fmt::Result {
+        let f = |i| match self.0.field(CellIndex(i)) {
+            Cell::Empty => config.empty,
+            Cell::PlayerOne => config.player_one,
+            Cell::PlayerTwo => config.player_two,
+        };
 
         write!(
             out,
@@ -36,6 +178,32 @@ impl TicTacToe {
         )
     }
 
+    /// The board as three rows from top to bottom, each holding its cells from left to right.
+    /// Empty cells are `-1`, stones of player one `0` and stones of player two `1`. Meant for
+    /// bindings to languages with dynamic arrays, e.g. Python lists.
+    pub fn to_rows(&self) -> Vec<Vec<i8>> {
+        (0..3)
+            .map(|row| {
+                (row * 3..row * 3 + 3)
+                    .map(|i| match self.0.field(CellIndex(i)) {
+                        Cell::Empty => -1,
+                        Cell::PlayerOne => 0,
+                        Cell::PlayerTwo => 1,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// All cells whose content satisfies `pred`, in ascending order. E.g. `|cell| cell ==
+    /// Cell::PlayerOne` yields the stones of player one, and `|cell| cell == Cell::Empty` the same
+    /// cells as [`Self::open_fields`].
+    pub fn cells_where(&self, pred: impl Fn(Cell) -> bool) -> Vec<CellIndex> {
+        let mut cells = Vec::with_capacity(9);
+        cells.extend((0..9).map(CellIndex).filter(|&i| pred(self.0.field(i))));
+        cells
+    }
+
     /// Iterator over all fields which are not occupied by a stone of either player
     pub fn open_fields(&self) -> impl Iterator<Item = CellIndex> + use<'_> {
         (0..9)
@@ -43,35 +211,440 @@ impl TicTacToe {
             .filter(move |&i| self.0.field(i) == Cell::Empty)
     }
 
+    /// Row and column of all fields which are not occupied by a stone of either player. Same order
+    /// as [`Self::open_fields`].
+    pub fn open_cells_rc(&self) -> impl Iterator<Item = (u8, u8)> + use<'_> {
+        self.open_fields().map(|i| (i.row(), i.column()))
+    }
+
+    /// Number of open fields in each row, from top to bottom.
+    pub fn open_per_row(&self) -> [u8; 3] {
+        let empty = !self.occupied_mask();
+        [0, 1, 2].map(|row| (empty >> (3 * row) & 0b111).count_ones() as u8)
+    }
+
+    /// Number of open fields in each column, from left to right.
+    pub fn open_per_column(&self) -> [u8; 3] {
+        let empty = !self.occupied_mask();
+        [0, 1, 2].map(|column| (empty >> column & 0b001_001_001).count_ones() as u8)
+    }
+
+    /// Number of moves played so far, i.e. the number of stones on the board.
+    pub const fn plies_played(&self) -> u8 {
+        self.0.stones()
+    }
+
+    /// `true` if no stone has been placed yet, i.e. for the starting position.
+    pub const fn is_empty(&self) -> bool {
+        self.0.stones() == 0
+    }
+
+    /// Number of empty cells left on the board. Moves remaining until the board is full, should
+    /// nobody win before.
+    pub const fn plies_remaining(&self) -> u8 {
+        9 - self.0.stones()
+    }
+
+    /// The player who has a stone in the cell, or `None` if the cell is empty.
+    pub fn owner(&self, index: CellIndex) -> Option<Player> {
+        match self.0.field(index) {
+            Cell::Empty => None,
+            Cell::PlayerOne => Some(Player::One),
+            Cell::PlayerTwo => Some(Player::Two),
+        }
+    }
+
+    /// The player whose turn it is. For finished games this is the player who would be next to
+    /// move.
+    pub fn current_player(&self) -> Player {
+        if self.0.stones().is_multiple_of(2) {
+            Player::One
+        } else {
+            Player::Two
+        }
+    }
+
+    /// Whether the game is won, drawn or whose turn it is. A game is drawn if nobody has won and
+    /// the player to move has no legal move left. For the standard board this is the case once
+    /// all nine cells are occupied.
     pub fn state(&self) -> TicTacToeState {
-        let stones = self.0.stones();
-        let player = stones % 2;
-        match (self.0.victory(), player) {
-            (true, 0) => TicTacToeState::VictoryPlayerTwo,
-            (true, 1) => TicTacToeState::VictoryPlayerOne,
-            (false, 0) => TicTacToeState::TurnPlayerOne,
-            _ => {
-                if stones == 9 {
-                    TicTacToeState::Draw
-                } else {
-                    TicTacToeState::TurnPlayerTwo
-                }
-            }
+        // The player who moved last is the only one who can have completed a line.
+        match (self.0.victory(), self.current_player()) {
+            (true, Player::One) => TicTacToeState::VictoryPlayerTwo,
+            (true, Player::Two) => TicTacToeState::VictoryPlayerOne,
+            (false, _) if !self.has_legal_moves() => TicTacToeState::Draw,
+            (false, Player::One) => TicTacToeState::TurnPlayerOne,
+            (false, Player::Two) => TicTacToeState::TurnPlayerTwo,
         }
     }
 
-    /// Places a stone for the current player in the specified Cell. Panics if cell is not empty
+    /// `true` if there is a cell the player to move could place a stone in, ignoring whether the
+    /// game has already been won.
+    pub(crate) fn has_legal_moves(&self) -> bool {
+        self.0.stones() < 9
+    }
+
+    /// `true` if the player to move can still play a move, i.e. the game is neither won nor drawn.
+    /// Same as `!self.state().is_terminal()`, but cheaper.
+    pub fn has_moves(&self) -> bool {
+        self.has_legal_moves() && !self.0.victory()
+    }
+
+    /// `true` if the position can be reached from the empty board by alternating moves, starting
+    /// with player one and stopping once a player has won.
+    pub fn is_legal_position(&self) -> bool {
+        let ones = self.0.mask(Player::One).count_ones();
+        let twos = self.0.mask(Player::Two).count_ones();
+        match (
+            self.0.victory_of(Player::One),
+            self.0.victory_of(Player::Two),
+        ) {
+            (false, false) => ones == twos || ones == twos + 1,
+            (true, false) => ones == twos + 1,
+            (false, true) => ones == twos,
+            (true, true) => false,
+        }
+    }
+
+    /// `true` if both boards hold the same stones and have the same [`Self::state`], i.e. the same
+    /// player to move. A board does not store whose turn it is, but derives it from the stones,
+    /// and [`Self::from_cells_with_turn`] rejects a player to move which contradicts them.
+    /// Therefore this never diverges from `==`. It states the intent of also comparing the turn,
+    /// e.g. for puzzle positions.
+    pub fn same_game_state(&self, other: &TicTacToe) -> bool {
+        self == other && self.state() == other.state()
+    }
+
+    /// `true` if the position could arise in a legal game with `player` to move. Player one moves
+    /// first, so it is the turn of player one if both players have the same number of stones and
+    /// the turn of player two if player one has exactly one stone more. Any other difference can
+    /// not arise. A player who has completed a line must have placed the last stone, so the
+    /// opponent must be the one to move. Both players completing a line is never consistent.
+    pub fn is_turn_consistent(&self, player: Player) -> bool {
+        self.is_legal_position() && self.current_player() == player
+    }
+
+    /// Places a stone for the current player in the specified Cell.
+    ///
+    /// The caller must ensure the cell is empty and the game is not finished yet. This is only
+    /// checked in debug builds, which panic on violation. Release builds skip the checks for speed.
+    /// Violating the contract never causes undefined behaviour, but leaves the board in an
+    /// unspecified state. Use [`Self::try_play_move`] for moves from untrusted sources.
     pub fn play_move(&mut self, &mov: &CellIndex) {
-        assert!(self.0.field(mov) == Cell::Empty);
-        let new_state = match self.state() {
-            TicTacToeState::TurnPlayerOne => Cell::PlayerOne,
-            TicTacToeState::TurnPlayerTwo => Cell::PlayerTwo,
-            _ => panic!("Tic Tac Toe game is already finished."),
+        debug_assert!(
+            self.0.field(mov) == Cell::Empty,
+            "Cell is already occupied."
+        );
+        debug_assert!(
+            !self.state().is_terminal(),
+            "Tic Tac Toe game is already finished."
+        );
+        let new_state = if self.0.stones().is_multiple_of(2) {
+            Cell::PlayerOne
+        } else {
+            Cell::PlayerTwo
         };
         self.0.mark_cell(mov, new_state);
     }
+
+    /// Places a stone for the current player in the specified Cell. In contrast to
+    /// [`Self::play_move`] the move is validated and the board is left untouched if it is illegal.
+    pub fn try_play_move(&mut self, mov: CellIndex) -> Result<(), MoveError> {
+        if self.state().is_terminal() {
+            return Err(MoveError::GameFinished);
+        }
+        if self.0.field(mov) != Cell::Empty {
+            return Err(MoveError::CellOccupied(mov));
+        }
+        self.play_move(&mov);
+        Ok(())
+    }
+
+    /// `true` if the current player completes a line by playing `mov`. `false` if `mov` is
+    /// occupied or the game is already finished.
+    pub fn is_winning_move(&self, mov: CellIndex) -> bool {
+        let mut child = *self;
+        child.try_play_move(mov).is_ok() && child.0.victory()
+    }
+
+    /// Repeatedly asks `choose` for a move and plays it, until the game is finished. Each move is
+    /// validated. If `choose` picks an illegal move, the board is left in the position it has been
+    /// picked in and the error is returned.
+    pub fn play_until_terminal(
+        &mut self,
+        mut choose: impl FnMut(&TicTacToe) -> CellIndex,
+    ) -> Result<GameResult, MoveError> {
+        loop {
+            if let Some(result) = self.state().result() {
+                return Ok(result);
+            }
+            let mov = choose(self);
+            self.try_play_move(mov)?;
+        }
+    }
+
+    /// Each legal move in ascending order, together with the state of the game after playing it.
+    /// Empty if the game is already finished.
+    pub fn move_previews(&self) -> impl Iterator<Item = (CellIndex, TicTacToeState)> + use<'_> {
+        let moves = if self.has_moves() { 0..9 } else { 0..0 };
+        moves
+            .map(CellIndex)
+            .filter(|&mov| self.0.field(mov) == Cell::Empty)
+            .map(|mov| {
+                let mut child = *self;
+                child.play_move(&mov);
+                (mov, child.state())
+            })
+    }
+
+    /// Plays `mov` and returns the resulting board, so moves can be chained. Fails like
+    /// [`Self::try_play_move`] if the move is illegal. Note that converting a `u8` panics for
+    /// values >= 9, see [`CellIndex::new`].
+    ///
+    /// ```
+    /// use tic_tac_toe_board::{MoveError, TicTacToe};
+    ///
+    /// let board = TicTacToe::new().then(4)?.then(0)?.then(8)?;
+    /// assert_eq!(board.plies_played(), 3);
+    /// # Ok::<(), MoveError>(())
+    /// ```
+    pub fn then(mut self, mov: impl Into<CellIndex>) -> Result<TicTacToe, MoveError> {
+        self.try_play_move(mov.into())?;
+        Ok(self)
+    }
+
+    /// Plays `moves` one after another, starting from this position, and yields the board after
+    /// each of them. The iterator ends early at the first illegal move, see
+    /// [`Self::try_play_move`], without yielding anything for it. Use
+    /// [`crate::validate_transcript`] to find out why a sequence has been cut short.
+    pub fn iter_playing(
+        self,
+        moves: impl IntoIterator<Item = CellIndex>,
+    ) -> impl Iterator<Item = TicTacToe> {
+        let mut board = self;
+        moves.into_iter().map_while(move |mov| {
+            board.try_play_move(mov).ok()?;
+            Some(board)
+        })
+    }
+
+    /// The three cells of the line completed by the winner, or `None` if no player has won. Should
+    /// the winner have completed two lines at once, the first one according to [`LINES`] is
+    /// returned.
+    pub fn winning_line(&self) -> Option<[CellIndex; 3]> {
+        LINES.iter().copied().find(|line| {
+            let first = self.0.field(line[0]);
+            first != Cell::Empty && line.iter().all(|&i| self.0.field(i) == first)
+        })
+    }
+
+    /// Every line completed by either player, in the order of [`LINES`]: Rows from top to bottom,
+    /// columns from left to right, then the diagonal and the anti diagonal. Legal positions hold
+    /// at most two lines, both completed with the same final stone. More lines, or lines of both
+    /// players, indicate a position which can not arise in a legal game.
+    pub fn all_winning_lines(&self) -> Vec<WinLine> {
+        let one = self.player_mask(Player::One);
+        let two = self.player_mask(Player::Two);
+        LINE_MASKS
+            .iter()
+            .enumerate()
+            .filter(|&(_, &line)| line & !one == 0 || line & !two == 0)
+            .map(|(i, _)| WinLine(i as u8))
+            .collect()
+    }
+
+    /// The three cells of `line`, same as [`WinLine::cells`].
+    pub fn line_cells(&self, line: WinLine) -> [CellIndex; 3] {
+        line.cells()
+    }
+
+    /// Content of the three cells of `line`, in the order of [`Self::line_cells`].
+    pub fn line_state(&self, line: WinLine) -> [Cell; 3] {
+        line.cells().map(|index| self.0.field(index))
+    }
+
+    /// `true` if the cell is part of the line returned by [`Self::winning_line`]. Always `false` if
+    /// nobody has won.
+    pub fn is_winning_cell(&self, index: CellIndex) -> bool {
+        self.winning_line()
+            .is_some_and(|line| line.contains(&index))
+    }
+
+    /// Cells occupied by `player` as a 9 bit mask. Bit `i` is set if the player has a stone in the
+    /// cell with index `i`, i.e. the least significant bit is the top left and bit 8 is the bottom
+    /// right cell.
+    ///
+    /// ```custom
+    /// 0 1 2
+    /// 3 4 5
+    /// 6 7 8
+    /// ```
+    pub fn player_mask(&self, player: Player) -> u16 {
+        self.0.mask(player)
+    }
+
+    /// Cells occupied by either player as a 9 bit mask, using the same mapping as
+    /// [`Self::player_mask`].
+    pub fn occupied_mask(&self) -> u16 {
+        self.0.mask(Player::One) | self.0.mask(Player::Two)
+    }
+
+    /// Occupancy of player one and player two, in this order, as two 9 bit masks. Bit `i` of
+    /// either mask is set if the respective player has a stone in the cell with index `i`, so bit
+    /// 0 is the top left and bit 8 the bottom right cell. Bits 9 to 15 are always zero. Same as
+    /// calling [`Self::player_mask`] for both players.
+    pub fn planes(&self) -> (u16, u16) {
+        (self.player_mask(Player::One), self.player_mask(Player::Two))
+    }
+
+    /// Number of lines `player` occupies completely. In legal positions this is at most two for
+    /// player one (completing two lines with the last stone) and at most one for player two.
+    pub fn completed_lines(&self, player: Player) -> u8 {
+        let mask = self.player_mask(player);
+        LINE_MASKS
+            .iter()
+            .filter(|&&line| mask & line == line)
+            .count() as u8
+    }
+
+    /// Number of distinct move sequences which lead from this position to a finished game. Lines
+    /// of play stop as soon as one player has won. A position which is already finished counts as
+    /// a single (empty) sequence.
+    pub fn count_complete_games(&self) -> u64 {
+        if self.state().is_terminal() {
+            return 1;
+        }
+        self.open_fields()
+            .map(|mov| {
+                let mut child = *self;
+                child.play_move(&mov);
+                child.count_complete_games()
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for TicTacToe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// Symbols used to print the cells of a board, see [`TicTacToe::print_with_config_to`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PrintConfig {
+    /// Printed for stones of player one
+    pub player_one: char,
+    /// Printed for stones of player two
+    pub player_two: char,
+    /// Printed for cells not occupied by either player
+    pub empty: char,
+}
+
+impl Default for PrintConfig {
+    /// `X` for player one, `O` for player two and a space for empty cells, just like
+    /// [`TicTacToe::print_to`].
+    fn default() -> PrintConfig {
+        PrintConfig {
+            player_one: 'X',
+            player_two: 'O',
+            empty: ' ',
+        }
+    }
+}
+
+/// Displays a board using custom symbols.
+struct Themed<'a> {
+    board: TicTacToe,
+    config: &'a PrintConfig,
+}
+
+impl fmt::Display for Themed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.board.write_with_config_to(f, self.config)
+    }
 }
 
+/// The eight lines of three cells which win the game. Rows from top to bottom, followed by columns
+/// from left to right, followed by the diagonal starting top left and the one starting top right.
+pub const LINES: [[CellIndex; 3]; 8] = [
+    [CellIndex(0), CellIndex(1), CellIndex(2)],
+    [CellIndex(3), CellIndex(4), CellIndex(5)],
+    [CellIndex(6), CellIndex(7), CellIndex(8)],
+    [CellIndex(0), CellIndex(3), CellIndex(6)],
+    [CellIndex(1), CellIndex(4), CellIndex(7)],
+    [CellIndex(2), CellIndex(5), CellIndex(8)],
+    [CellIndex(0), CellIndex(4), CellIndex(8)],
+    [CellIndex(2), CellIndex(4), CellIndex(6)],
+];
+
+/// One of the eight [`LINES`], named by its orientation.
+///
+/// ```
+/// use tic_tac_toe_board::{CellIndex, WinLine, LINES};
+///
+/// assert_eq!(WinLine::row(1).cells(), LINES[1]);
+/// assert_eq!(WinLine::ANTI_DIAGONAL.cells(), [2, 4, 6].map(CellIndex::new));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WinLine(u8);
+
+impl WinLine {
+    /// Diagonal from the top left to the bottom right
+    pub const DIAGONAL: WinLine = WinLine(6);
+    /// Diagonal from the top right to the bottom left
+    pub const ANTI_DIAGONAL: WinLine = WinLine(7);
+
+    /// Row from top (0) to bottom (2). Panics for values >= 3.
+    pub fn row(row: u8) -> WinLine {
+        assert!(row < 3);
+        WinLine(row)
+    }
+
+    /// Column from left (0) to right (2). Panics for values >= 3.
+    pub fn column(column: u8) -> WinLine {
+        assert!(column < 3);
+        WinLine(3 + column)
+    }
+
+    /// The three cells of the line in ascending order
+    pub fn cells(self) -> [CellIndex; 3] {
+        LINES[self.0 as usize]
+    }
+}
+
+/// The cells of each of the [`LINES`] as a mask in the format of [`TicTacToe::player_mask`].
+const LINE_MASKS: [u16; 8] = [
+    0b000_000_111,
+    0b000_111_000,
+    0b111_000_000,
+    0b001_001_001,
+    0b010_010_010,
+    0b100_100_100,
+    0b100_010_001,
+    0b001_010_100,
+];
+
+/// Reasons why a move can not be played
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveError {
+    /// The game is already finished, so no more moves can be played.
+    GameFinished,
+    /// The cell already contains a stone.
+    CellOccupied(CellIndex),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::GameFinished => write!(f, "Tic Tac Toe game is already finished."),
+            MoveError::CellOccupied(index) => write!(f, "The {} is already occupied.", index),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TicTacToeState {
     VictoryPlayerOne,
@@ -91,16 +664,53 @@ impl TicTacToeState {
             TicTacToeState::TurnPlayerOne | Self::TurnPlayerTwo => false,
         }
     }
+
+    /// Outcome of the game if it is finished, `None` if it is still ongoing
+    pub fn result(self) -> Option<GameResult> {
+        match self {
+            TicTacToeState::VictoryPlayerOne => Some(GameResult::VictoryPlayerOne),
+            TicTacToeState::VictoryPlayerTwo => Some(GameResult::VictoryPlayerTwo),
+            TicTacToeState::Draw => Some(GameResult::Draw),
+            TicTacToeState::TurnPlayerOne | TicTacToeState::TurnPlayerTwo => None,
+        }
+    }
+}
+
+/// Outcome of a finished game
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum GameResult {
+    VictoryPlayerOne,
+    VictoryPlayerTwo,
+    Draw,
+}
+
+/// One of the two players of a game of TicTacToe
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Player {
+    /// Player who moves first. Stones are printed as `X`.
+    One,
+    /// Player who moves second. Stones are printed as `O`.
+    Two,
+}
+
+impl Player {
+    /// The other player
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
 }
 
 /// State of a cell in a TicTacToe Board
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Cell {
+pub enum Cell {
     /// Field is not captured by either player
     Empty,
     /// Field contains a stone from Player 1
     PlayerOne,
-    /// Field contains a stone from Player 1
+    /// Field contains a stone from Player 2
     PlayerTwo,
 }
 
@@ -138,6 +748,24 @@ impl CellIndex {
         CellIndex(index)
     }
 
+    /// Like [`Self::new`], but fails instead of panicking for values >= 9. Use this to validate
+    /// untrusted input. Note that `CellIndex::try_from(u8)` is no safe check: it is derived from
+    /// the [`From<u8>`] implementation, which is only kept for compatibility, and therefore panics
+    /// for values >= 9 rather than returning an error.
+    pub fn try_new(index: u8) -> Result<CellIndex, &'static str> {
+        if index < 9 {
+            Ok(CellIndex(index))
+        } else {
+            Err("Only numbers from 0 to 8 are valid cell indices.")
+        }
+    }
+
+    /// Like [`Self::new`], but never panics. Values >= 9 are clamped to 8, the bottom right
+    /// corner. Useful for total conversions, e.g. from a slider in a user interface.
+    pub fn saturating(index: u8) -> CellIndex {
+        CellIndex(index.min(8))
+    }
+
     pub fn row(self) -> u8 {
         self.0 / 3
     }
@@ -145,8 +773,129 @@ impl CellIndex {
     pub fn column(self) -> u8 {
         self.0 % 3
     }
+
+    /// Parses `"row,column"`, with both components ranging from 0 to 2 and optional whitespace
+    /// around them. E.g. `"1,2"` or `"1, 2"` is the cell with index 5.
+    pub fn from_coord_pair(source: &str) -> Result<CellIndex, &'static str> {
+        let (row, column) = source
+            .split_once(',')
+            .ok_or("Expected row and column separated by a comma, e.g. \"1,2\".")?;
+        let component = |c: &str| match c.trim().parse::<u8>() {
+            Ok(v @ 0..=2) => Ok(v),
+            _ => Err("Row and column must each be a number from 0 to 2."),
+        };
+        Ok(CellIndex(component(row)? * 3 + component(column)?))
+    }
+
+    /// Inverse of [`Self::from_coord_pair`], e.g. `"1,2"` for the cell with index 5.
+    pub fn to_coord_pair(self) -> String {
+        format!("{},{}", self.row(), self.column())
+    }
+
+    /// Parses algebraic notation like `"a1"`, as known from chess. The letter names the column from
+    /// left (`a`) to right (`c`), the digit the row from bottom (`1`) to top (`3`). So `"a3"` is
+    /// the top left corner and `"c1"` the bottom right. Letters are accepted in either case.
+    pub fn from_algebraic(source: &str) -> Result<CellIndex, &'static str> {
+        match source.trim().as_bytes() {
+            &[column @ (b'a'..=b'c' | b'A'..=b'C'), row @ b'1'..=b'3'] => {
+                let column = column.to_ascii_lowercase() - b'a';
+                Ok(CellIndex((b'3' - row) * 3 + column))
+            }
+            _ => Err("Expected a column from a to c followed by a row from 1 to 3, e.g. \"b2\"."),
+        }
+    }
+
+    /// Inverse of [`Self::from_algebraic`], e.g. `"a3"` for the top left corner.
+    pub fn to_algebraic(self) -> String {
+        format!("{}{}", (b'a' + self.column()) as char, 3 - self.row())
+    }
+
+    /// Parses a cell in any of the supported notations. Tries them in this order and returns the
+    /// first success:
+    ///
+    /// 1. The index as a single digit from 0 to 8, e.g. `"5"`. In contrast to [`str::parse`]
+    ///    nothing but surrounding whitespace may follow the digit.
+    /// 2. Row and column, see [`Self::from_coord_pair`], e.g. `"1,2"`.
+    /// 3. Algebraic notation, see [`Self::from_algebraic`], e.g. `"c2"`.
+    ///
+    /// The notations do not overlap, so every input is parsed by at most one of them.
+    pub fn parse_any(source: &str) -> Result<CellIndex, ParseError> {
+        let index = match source.trim().as_bytes() {
+            &[digit @ b'0'..=b'8'] => return Ok(CellIndex(digit - b'0')),
+            _ => "Expected a single digit from 0 to 8.",
+        };
+        let coord_pair = match CellIndex::from_coord_pair(source) {
+            Ok(cell) => return Ok(cell),
+            Err(error) => error,
+        };
+        let algebraic = match CellIndex::from_algebraic(source) {
+            Ok(cell) => return Ok(cell),
+            Err(error) => error,
+        };
+        Err(ParseError {
+            index,
+            coord_pair,
+            algebraic,
+        })
+    }
+
+    /// Line and column, both starting at zero, of the character representing this cell in the
+    /// grid written by [`TicTacToe::print_to`]. Lines are separated by `\n` and every character of
+    /// the grid takes up one column.
+    pub fn display_position(self) -> (usize, usize) {
+        (
+            usize::from(self.row()) * 2 + 1,
+            usize::from(self.column()) * 2 + 1,
+        )
+    }
+
+    /// Name of the cell in plain words, e.g. `"top-left corner"`, `"left edge"` or `"center"`.
+    /// Suitable for narrating a game, e.g. for screen readers.
+    pub fn describe(self) -> &'static str {
+        match self.0 {
+            0 => "top-left corner",
+            1 => "top edge",
+            2 => "top-right corner",
+            3 => "left edge",
+            4 => "center",
+            5 => "right edge",
+            6 => "bottom-left corner",
+            7 => "bottom edge",
+            _ => "bottom-right corner",
+        }
+    }
+
+    /// This cell as a mask in the format of [`TicTacToe::player_mask`].
+    pub(crate) fn mask(self) -> u16 {
+        1 << self.0
+    }
 }
 
+/// Returned by [`CellIndex::parse_any`] if the input matches none of the notations. Holds the
+/// reason for each of them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Why the input is not a single digit index
+    pub index: &'static str,
+    /// Why the input is not a pair of row and column, see [`CellIndex::from_coord_pair`]
+    pub coord_pair: &'static str,
+    /// Why the input is not in algebraic notation, see [`CellIndex::from_algebraic`]
+    pub algebraic: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Input is not a valid cell in any notation. As index: {} As row and column: {} In \
+            algebraic notation: {}",
+            self.index, self.coord_pair, self.algebraic
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl std::str::FromStr for CellIndex {
     type Err = &'static str;
 
@@ -164,6 +913,8 @@ impl fmt::Display for CellIndex {
     }
 }
 
+/// Panics for values >= 9, just like [`CellIndex::new`]. Prefer [`CellIndex::try_new`] to
+/// validate and [`CellIndex::saturating`] for a conversion which never fails.
 impl From<u8> for CellIndex {
     fn from(source: u8) -> CellIndex {
         match source {
@@ -176,6 +927,8 @@ impl From<u8> for CellIndex {
 #[cfg(test)]
 mod test {
 
+    use std::collections::HashSet;
+
     use super::*;
 
     #[test]
@@ -214,6 +967,159 @@ mod test {
         assert_eq!(String::from_utf8(buf).unwrap(), expected);
     }
 
+    #[test]
+    fn write_to_and_display_match_print_to() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(6));
+        let mut buf = Vec::new();
+        board.print_to(&mut buf).unwrap();
+        let printed = String::from_utf8(buf).unwrap();
+
+        let mut written = String::new();
+        board.write_to(&mut written).unwrap();
+
+        assert_eq!(written, printed);
+        assert_eq!(board.to_string(), printed);
+    }
+
+    #[test]
+    fn print_with_custom_symbols() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(6));
+        let config = PrintConfig {
+            player_one: '🐱',
+            player_two: '🐶',
+            empty: '.',
+        };
+        let mut buf = Vec::new();
+        board.print_with_config_to(&mut buf, &config).unwrap();
+
+        let expected = "-------\n\
+                        |.|.|.|\n\
+                        |-----|\n\
+                        |.|🐱|.|\n\
+                        |-----|\n\
+                        |🐶|.|.|\n\
+                        -------";
+
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn algebraic_notation() {
+        assert_eq!(CellIndex::from_algebraic("a3"), Ok(CellIndex::new(0)));
+        assert_eq!(CellIndex::from_algebraic("C1"), Ok(CellIndex::new(8)));
+        assert!(CellIndex::from_algebraic("d1").is_err());
+        assert!(CellIndex::from_algebraic("a4").is_err());
+        for i in 0..9 {
+            let index = CellIndex::new(i);
+            assert_eq!(CellIndex::from_algebraic(&index.to_algebraic()), Ok(index));
+        }
+    }
+
+    #[test]
+    fn parse_any_notation() {
+        assert_eq!(CellIndex::parse_any(" 5 "), Ok(CellIndex::new(5)));
+        // Not a single digit, so not confused with index 1
+        assert_eq!(CellIndex::parse_any("1,2"), Ok(CellIndex::new(5)));
+        assert_eq!(CellIndex::parse_any("b2"), Ok(CellIndex::new(4)));
+        let error = CellIndex::parse_any("12").unwrap_err();
+        assert_eq!(error.index, "Expected a single digit from 0 to 8.");
+        assert!(CellIndex::parse_any("9").is_err());
+    }
+
+    #[test]
+    fn display_position_matches_printed_grid() {
+        for i in 0..9 {
+            let index = CellIndex::new(i);
+            let board = TicTacToe::from_opening(index);
+            let mut buf = Vec::new();
+            board.print_to(&mut buf).unwrap();
+            let printed = String::from_utf8(buf).unwrap();
+
+            let (line, column) = index.display_position();
+            let symbol = printed.lines().nth(line).unwrap().chars().nth(column);
+            assert_eq!(symbol, Some('X'));
+            assert_eq!(printed.matches('X').count(), 1);
+        }
+    }
+
+    #[test]
+    fn conversions_from_u8() {
+        assert_eq!(CellIndex::try_new(8), Ok(CellIndex::new(8)));
+        assert!(CellIndex::try_new(9).is_err());
+        assert_eq!(CellIndex::saturating(3), CellIndex::new(3));
+        assert_eq!(CellIndex::saturating(200), CellIndex::new(8));
+        assert_eq!(CellIndex::from(4), CellIndex::new(4));
+    }
+
+    #[test]
+    fn describe_cells() {
+        assert_eq!(CellIndex::new(0).describe(), "top-left corner");
+        assert_eq!(CellIndex::new(4).describe(), "center");
+        assert_eq!(CellIndex::new(7).describe(), "bottom edge");
+        let distinct: HashSet<_> = (0..9).map(|i| CellIndex::new(i).describe()).collect();
+        assert_eq!(distinct.len(), 9);
+    }
+
+    #[test]
+    fn coord_pair_notation() {
+        assert_eq!(CellIndex::from_coord_pair("1,2"), Ok(CellIndex::new(5)));
+        assert_eq!(CellIndex::from_coord_pair(" 2 , 0 "), Ok(CellIndex::new(6)));
+        assert!(CellIndex::from_coord_pair("3,0").is_err());
+        assert!(CellIndex::from_coord_pair("1").is_err());
+        assert!(CellIndex::from_coord_pair("1,2,0").is_err());
+        for i in 0..9 {
+            let index = CellIndex::new(i);
+            assert_eq!(
+                CellIndex::from_coord_pair(&index.to_coord_pair()),
+                Ok(index)
+            );
+        }
+    }
+
+    #[test]
+    fn print_result_brackets_winning_line() {
+        let mut game = TicTacToe::new();
+        for i in [4, 6, 2, 8, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let mut buf = Vec::new();
+        game.print_result_to(&mut buf).unwrap();
+
+        let expected = "-------------\n\
+                        |   |   | X |\n\
+                        |-----------|\n\
+                        |   | X | X |\n\
+                        |-----------|\n\
+                        |[O]|[O]|[O]|\n\
+                        -------------";
+
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn print_result_of_draw_has_no_brackets() {
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let mut buf = Vec::new();
+        game.print_result_to(&mut buf).unwrap();
+
+        let expected = "-------------\n\
+                        | X | O | X |\n\
+                        |-----------|\n\
+                        | X | O | O |\n\
+                        |-----------|\n\
+                        | O | X | X |\n\
+                        -------------";
+
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
     #[test]
     fn victory_condition_player_two() {
         // -------
@@ -233,4 +1139,380 @@ mod test {
 
         assert_eq!(game.state(), TicTacToeState::VictoryPlayerTwo);
     }
+
+    #[test]
+    fn try_play_move_rejects_illegal_moves() {
+        let mut game = TicTacToe::new();
+        game.try_play_move(CellIndex::new(4)).unwrap();
+        assert_eq!(
+            game.try_play_move(CellIndex::new(4)),
+            Err(MoveError::CellOccupied(CellIndex::new(4)))
+        );
+        // Player one wins with 2, 4 and 6
+        for i in [0, 2, 1, 6] {
+            game.try_play_move(CellIndex::new(i)).unwrap();
+        }
+        assert_eq!(game.state(), TicTacToeState::VictoryPlayerOne);
+        let before = game;
+        assert_eq!(
+            game.try_play_move(CellIndex::new(3)),
+            Err(MoveError::GameFinished)
+        );
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn open_cells_as_row_and_column() {
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let open: Vec<_> = game.open_cells_rc().collect();
+        assert_eq!(open, [(2, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn open_fields_per_row_and_column() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.open_per_row(), [3, 3, 3]);
+        for i in [0, 1, 2, 4, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(game.open_per_row(), [0, 2, 2]);
+        assert_eq!(game.open_per_column(), [2, 0, 2]);
+    }
+
+    #[test]
+    fn plies_played_and_remaining() {
+        let mut game = TicTacToe::new();
+        assert_eq!((game.plies_played(), game.plies_remaining()), (0, 9));
+        assert!(game.is_empty());
+        game.play_move(&CellIndex::new(4));
+        assert!(!game.is_empty());
+        game.play_move(&CellIndex::new(0));
+        assert_eq!((game.plies_played(), game.plies_remaining()), (2, 7));
+    }
+
+    #[test]
+    fn owner_of_cells() {
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(6));
+        assert_eq!(game.owner(CellIndex::new(4)), Some(Player::One));
+        assert_eq!(game.owner(CellIndex::new(6)), Some(Player::Two));
+        assert_eq!(game.owner(CellIndex::new(0)), None);
+    }
+
+    #[test]
+    fn winning_moves_for_player_one() {
+        let mut game = TicTacToe::new();
+        // Player one threatens 2, 4, 6
+        for i in [4, 0, 2, 1] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert!(game.is_winning_move(CellIndex::new(6)));
+        assert!(!game.is_winning_move(CellIndex::new(5)));
+        // Occupied
+        assert!(!game.is_winning_move(CellIndex::new(4)));
+
+        game.play_move(&CellIndex::new(6));
+        // Finished
+        assert!(!game.is_winning_move(CellIndex::new(8)));
+    }
+
+    #[test]
+    fn play_until_terminal_picking_lowest_open_field() {
+        let mut game = TicTacToe::new();
+        let result = game.play_until_terminal(|board| board.open_fields().next().unwrap());
+        // Player one completes the diagonal 2, 4, 6
+        assert_eq!(result, Ok(GameResult::VictoryPlayerOne));
+        assert_eq!(game.open_fields().count(), 2);
+    }
+
+    #[test]
+    fn preview_resulting_states() {
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let previews: Vec<_> = game.move_previews().collect();
+        assert_eq!(
+            previews,
+            [
+                (CellIndex::new(6), TicTacToeState::TurnPlayerOne),
+                (CellIndex::new(8), TicTacToeState::TurnPlayerOne)
+            ]
+        );
+        game.play_move(&CellIndex::new(8));
+        let previews: Vec<_> = game.move_previews().collect();
+        assert_eq!(
+            previews,
+            [(CellIndex::new(6), TicTacToeState::VictoryPlayerOne)]
+        );
+        game.play_move(&CellIndex::new(6));
+        assert_eq!(game.move_previews().count(), 0);
+    }
+
+    #[test]
+    fn iter_playing_yields_each_position() {
+        let positions: Vec<_> = TicTacToe::new()
+            .iter_playing([4, 0].map(CellIndex::new))
+            .collect();
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].owner(CellIndex::new(4)), Some(Player::One));
+        assert_eq!(positions[1].owner(CellIndex::new(0)), Some(Player::Two));
+    }
+
+    #[test]
+    fn chain_moves() {
+        let board = TicTacToe::new()
+            .then(4)
+            .and_then(|b| b.then(CellIndex::new(0)));
+        assert_eq!(
+            board.ok(),
+            TicTacToe::from_openings(&[4, 0].map(CellIndex::new)).ok()
+        );
+        let occupied = TicTacToe::new().then(4).and_then(|b| b.then(4));
+        assert_eq!(occupied, Err(MoveError::CellOccupied(CellIndex::new(4))));
+    }
+
+    #[test]
+    fn iter_playing_stops_at_illegal_move() {
+        let moves = [4, 0, 4, 8].map(CellIndex::new);
+        assert_eq!(TicTacToe::new().iter_playing(moves).count(), 2);
+    }
+
+    #[test]
+    fn play_until_terminal_with_illegal_choice() {
+        let mut game = TicTacToe::new();
+        let result = game.play_until_terminal(|_| CellIndex::new(4));
+        assert_eq!(result, Err(MoveError::CellOccupied(CellIndex::new(4))));
+        assert_eq!(game.open_fields().count(), 8);
+    }
+
+    #[test]
+    fn winning_line_of_player_two() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.winning_line(), None);
+        for i in [4, 6, 2, 8, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(
+            game.winning_line(),
+            Some([CellIndex::new(6), CellIndex::new(7), CellIndex::new(8)])
+        );
+    }
+
+    #[test]
+    fn draw_once_no_legal_move_is_left() {
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7, 6] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert!(game.has_legal_moves());
+        assert_eq!(game.state(), TicTacToeState::TurnPlayerOne);
+
+        game.play_move(&CellIndex::new(8));
+        assert!(!game.has_legal_moves());
+        assert!(!game.has_moves());
+        assert_eq!(game.state(), TicTacToeState::Draw);
+
+        // A victory takes precedence, even if no legal move is left afterwards
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 3, 4, 5, 7, 6, 8] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert!(!game.has_legal_moves());
+        assert_eq!(game.state(), TicTacToeState::VictoryPlayerOne);
+    }
+
+    #[test]
+    fn same_game_state_agrees_with_equality() {
+        let one = TicTacToe::from_openings(&[4, 0].map(CellIndex::new)).unwrap();
+        let two = TicTacToe::from_openings(&[4, 8].map(CellIndex::new)).unwrap();
+        let cells = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| one.0.field(CellIndex::new(i)));
+        let set_up = TicTacToe::from_cells_with_turn(cells, Player::One).unwrap();
+
+        assert!(one.same_game_state(&set_up));
+        assert!(!one.same_game_state(&two));
+    }
+
+    #[test]
+    fn turn_consistency() {
+        let mut game = TicTacToe::new();
+        assert!(game.is_turn_consistent(Player::One));
+        assert!(!game.is_turn_consistent(Player::Two));
+        for i in [0, 3, 1, 4, 2] {
+            game.play_move(&CellIndex::new(i));
+        }
+        // Player one has won, so the loser would be next
+        assert!(game.is_turn_consistent(Player::Two));
+
+        // A single stone of player two can not arise, whoever is to move
+        let mut illegal = TicTacToe::new();
+        illegal.0.mark_cell(CellIndex::new(0), Cell::PlayerTwo);
+        assert!(!illegal.is_turn_consistent(Player::One));
+        assert!(!illegal.is_turn_consistent(Player::Two));
+    }
+
+    #[test]
+    fn query_cells_by_content() {
+        let board = TicTacToe::from_openings(&[4, 0, 8].map(CellIndex::new)).unwrap();
+        assert_eq!(
+            board.cells_where(|cell| cell == Cell::PlayerOne),
+            [4, 8].map(CellIndex::new)
+        );
+        assert_eq!(
+            board.cells_where(|cell| cell != Cell::Empty),
+            [0, 4, 8].map(CellIndex::new)
+        );
+        let empty: Vec<_> = board.open_fields().collect();
+        assert_eq!(board.cells_where(|cell| cell == Cell::Empty), empty);
+    }
+
+    #[test]
+    fn rows_of_integers() {
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(2));
+        assert_eq!(
+            game.to_rows(),
+            vec![vec![-1, -1, 1], vec![-1, 0, -1], vec![-1, -1, -1]]
+        );
+    }
+
+    #[test]
+    fn no_moves_after_victory() {
+        let mut game = TicTacToe::new();
+        assert!(game.has_moves());
+        for i in [0, 3, 1, 4] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert!(game.has_moves());
+        game.play_move(&CellIndex::new(2));
+        // Four cells are still empty, but player one has won
+        assert!(!game.has_moves());
+    }
+
+    #[test]
+    fn count_completed_lines() {
+        // -------
+        // |X|X|X|
+        // |-----|
+        // |O|X|O|
+        // |-----|
+        // |X|O|O|
+        // -------
+        let board = TicTacToe::from_grid("XXX/OXO/XOO", GridConfig::default()).unwrap();
+        // Top row and anti diagonal
+        assert_eq!(board.completed_lines(Player::One), 2);
+        assert_eq!(board.completed_lines(Player::Two), 0);
+        assert_eq!(TicTacToe::new().completed_lines(Player::One), 0);
+    }
+
+    #[test]
+    fn winning_cells_agree_with_winning_line() {
+        let mut game = TicTacToe::new();
+        for i in [4, 6, 2, 8, 5] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert!((0..9).all(|i| !game.is_winning_cell(CellIndex::new(i))));
+
+        game.play_move(&CellIndex::new(7));
+        let line = game.winning_line().unwrap();
+        for i in (0..9).map(CellIndex::new) {
+            assert_eq!(game.is_winning_cell(i), line.contains(&i));
+        }
+    }
+
+    #[test]
+    fn same_position_built_differently_hashes_equally() {
+        let hash = |board: &TicTacToe| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+        let mut played = TicTacToe::new();
+        for i in [4, 0, 8] {
+            played.play_move(&CellIndex::new(i));
+        }
+        let mut other_order = TicTacToe::new();
+        for i in [8, 0, 4] {
+            other_order.play_move(&CellIndex::new(i));
+        }
+        let parsed = TicTacToe::from_grid("O../.X./..X", GridConfig::default()).unwrap();
+
+        assert_eq!(played, other_order);
+        assert_eq!(played, parsed);
+        assert_eq!(hash(&played), hash(&other_order));
+        assert_eq!(hash(&played), hash(&parsed));
+    }
+
+    #[test]
+    fn state_of_named_lines() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(2));
+
+        assert_eq!(
+            board.line_state(WinLine::ANTI_DIAGONAL),
+            [Cell::PlayerTwo, Cell::PlayerOne, Cell::Empty]
+        );
+        assert_eq!(
+            board.line_state(WinLine::column(2)),
+            [Cell::PlayerTwo, Cell::Empty, Cell::Empty]
+        );
+        assert_eq!(
+            board.line_cells(WinLine::column(2)),
+            [2, 5, 8].map(CellIndex::new)
+        );
+    }
+
+    #[test]
+    fn every_completed_line() {
+        let config = GridConfig::default();
+        assert!(TicTacToe::new().all_winning_lines().is_empty());
+
+        // The last stone in the corner completes two lines at once
+        let board = TicTacToe::from_grid("XXX/OOX/OOX", config).unwrap();
+        assert_eq!(
+            board.all_winning_lines(),
+            [WinLine::row(0), WinLine::column(2)]
+        );
+
+        // Both players completed a row, which can not happen in a legal game
+        let mut corrupt = TicTacToe::new();
+        for i in 0..3 {
+            corrupt.0.mark_cell(CellIndex::new(i), Cell::PlayerOne);
+            corrupt.0.mark_cell(CellIndex::new(i + 6), Cell::PlayerTwo);
+        }
+        assert_eq!(
+            corrupt.all_winning_lines(),
+            [WinLine::row(0), WinLine::row(2)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_range() {
+        WinLine::row(3);
+    }
+
+    #[test]
+    fn count_complete_games_from_empty_board() {
+        assert_eq!(TicTacToe::new().count_complete_games(), 255168);
+    }
+
+    #[test]
+    fn occupancy_masks() {
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(4));
+        game.play_move(&CellIndex::new(0));
+        game.play_move(&CellIndex::new(8));
+
+        assert_eq!(game.player_mask(Player::One), 0b100_010_000);
+        assert_eq!(game.player_mask(Player::Two), 0b000_000_001);
+        assert_eq!(game.occupied_mask(), 0b100_010_001);
+        assert_eq!(game.planes(), (0b100_010_000, 0b000_000_001));
+    }
 }
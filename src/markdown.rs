@@ -0,0 +1,60 @@
+use crate::{Cell, CellIndex, TicTacToe};
+
+impl TicTacToe {
+    /// Renders the board as a GitHub flavored Markdown table with centered columns. Markdown
+    /// tables require a header, so the table starts with an empty header row, followed by the
+    /// three rows of the board. Occupied cells contain `X` or `O`, empty cells a space.
+    ///
+    /// ```
+    /// use tic_tac_toe_board::{CellIndex, TicTacToe};
+    ///
+    /// let mut board = TicTacToe::new();
+    /// board.play_move(&CellIndex::new(4));
+    /// assert_eq!(
+    ///     board.to_markdown(),
+    ///     "|   |   |   |\n\
+    ///      |:-:|:-:|:-:|\n\
+    ///      |   |   |   |\n\
+    ///      |   | X |   |\n\
+    ///      |   |   |   |\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("|   |   |   |\n|:-:|:-:|:-:|\n");
+        for row in 0..3 {
+            markdown.push('|');
+            for index in (row * 3..row * 3 + 3).map(CellIndex) {
+                let symbol = match self.0.field(index) {
+                    Cell::Empty => ' ',
+                    Cell::PlayerOne => 'X',
+                    Cell::PlayerTwo => 'O',
+                };
+                markdown.push(' ');
+                markdown.push(symbol);
+                markdown.push_str(" |");
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn markdown_of_board_with_two_stones() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(8));
+
+        let expected = "|   |   |   |\n\
+                        |:-:|:-:|:-:|\n\
+                        | X |   |   |\n\
+                        |   |   |   |\n\
+                        |   |   | O |\n";
+        assert_eq!(board.to_markdown(), expected);
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::HashSet;
+
+use crate::{Cell, CellIndex, GameResult, TicTacToe, TicTacToeState};
+
+/// Every finished position (a win for either player or a full board draw) which can be reached
+/// from the empty board by legal play. Each position is yielded exactly once, no matter how many
+/// different move orders lead to it. No symmetry reduction is applied, i.e. positions which only
+/// differ by a rotation or reflection of the board are yielded individually.
+pub fn terminal_positions() -> impl Iterator<Item = TicTacToe> {
+    reachable_positions()
+        .into_iter()
+        .filter(|board| board.state().is_terminal())
+}
+
+/// Every position which can be reached from the empty board by legal play, including the empty
+/// board itself and finished games. Each position is yielded exactly once, no matter how many
+/// different move orders lead to it. No symmetry reduction is applied. Yields the 5478 positions
+/// accepted by [`TicTacToe::is_legal_position`].
+pub fn all_legal_positions() -> impl Iterator<Item = TicTacToe> {
+    reachable_positions().into_iter()
+}
+
+/// Like [`all_legal_positions`], but positions which only differ by rotation or reflection of the
+/// board are yielded once, represented by their [`TicTacToe::canonical`] form. Yields 765
+/// positions.
+pub fn all_legal_positions_canonical() -> impl Iterator<Item = TicTacToe> {
+    let mut seen = HashSet::new();
+    reachable_positions()
+        .into_iter()
+        .map(|board| board.canonical())
+        .filter(move |&board| seen.insert(board))
+}
+
+/// All positions of [`all_legal_positions`] with exactly `n` stones on the board, finished or
+/// not. Each position is yielded once. Empty for `n > 9`.
+pub fn boards_of_ply(n: u8) -> impl Iterator<Item = TicTacToe> {
+    all_legal_positions().filter(move |board| board.plies_played() == n)
+}
+
+/// Number of distinct positions which can be reached from the empty board by legal play,
+/// including the empty board itself and finished games. Positions which only differ by rotation
+/// or reflection are counted individually.
+pub fn count_distinct_positions() -> usize {
+    all_legal_positions().count()
+}
+
+/// Like [`count_distinct_positions`], but positions which only differ by rotation or reflection
+/// of the board are counted once.
+pub fn count_distinct_positions_canonical() -> usize {
+    all_legal_positions_canonical().count()
+}
+
+/// Splits `boards` into positions which can arise in a legal game and positions which can not,
+/// according to [`TicTacToe::is_legal_position`]. Both keep the order of the input.
+pub fn partition_legal(boards: &[TicTacToe]) -> (Vec<TicTacToe>, Vec<TicTacToe>) {
+    boards.iter().partition(|board| board.is_legal_position())
+}
+
+/// Complete games, i.e. move sequences from the empty board to a finished position, where moves
+/// are identified up to symmetry. At each ply, moves which lead to positions equal up to rotation
+/// and reflection of the board (see [`TicTacToe::canonical`]) are treated as one and only the
+/// lowest of them is followed. This reduces the 255168 games counted by
+/// [`TicTacToe::count_complete_games`] to 26830. Each game is only yielded in its representative
+/// form, in depth first order trying the lowest moves first.
+pub fn distinct_games_canonical_iter() -> impl Iterator<Item = Vec<CellIndex>> {
+    game_lines(true)
+}
+
+/// Every complete game, i.e. every sequence of legal moves from the empty board which ends in a
+/// finished position. Lines of play stop as soon as one player has won. Ordered by a depth first
+/// search, which tries moves in ascending order. Yields as many games as
+/// [`TicTacToe::count_complete_games`] counts from the empty board, i.e. 255168.
+pub fn all_game_lines() -> impl Iterator<Item = Vec<CellIndex>> {
+    game_lines(false)
+}
+
+/// Depth first enumeration of complete games, trying the lowest moves first. If
+/// `symmetry_reduced` is `true`, only the lowest of all moves leading to symmetric positions is
+/// followed.
+fn game_lines(symmetry_reduced: bool) -> impl Iterator<Item = Vec<CellIndex>> {
+    // Each entry is a line of play together with the position it leads to.
+    let mut stack = vec![(Vec::new(), TicTacToe::new())];
+    std::iter::from_fn(move || {
+        while let Some((moves, board)) = stack.pop() {
+            if board.state().is_terminal() {
+                return Some(moves);
+            }
+            let mut seen = HashSet::new();
+            let children: Vec<_> = board
+                .open_fields()
+                .filter_map(|mov| {
+                    let mut child = board;
+                    child.play_move(&mov);
+                    (!symmetry_reduced || seen.insert(child.canonical())).then_some((mov, child))
+                })
+                .collect();
+            // Push in reverse, so the lowest move is explored first.
+            for (mov, child) in children.into_iter().rev() {
+                let mut moves = moves.clone();
+                moves.push(mov);
+                stack.push((moves, child));
+            }
+        }
+        None
+    })
+}
+
+/// Number of complete games up to symmetry, as yielded by [`distinct_games_canonical_iter`].
+pub fn distinct_games_canonical() -> usize {
+    distinct_games_canonical_iter().count()
+}
+
+/// Every position reachable from the empty board by legal play, each exactly once. Ordered by
+/// a depth first search, which tries moves in ascending order.
+fn reachable_positions() -> Vec<TicTacToe> {
+    let mut seen = HashSet::new();
+    let mut positions = Vec::new();
+    let mut stack = vec![TicTacToe::new()];
+    while let Some(board) = stack.pop() {
+        if !seen.insert(board) {
+            continue;
+        }
+        positions.push(board);
+        if board.state().is_terminal() {
+            continue;
+        }
+        // Push in reverse, so the lowest move is explored first.
+        for mov in board.open_fields().collect::<Vec<_>>().into_iter().rev() {
+            let mut child = board;
+            child.play_move(&mov);
+            stack.push(child);
+        }
+    }
+    positions
+}
+
+/// Number of game continuations ending in each result, see [`TicTacToe::outcome_counts`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct OutcomeCounts {
+    /// Continuations won by player one
+    pub player_one_wins: u64,
+    /// Continuations won by player two
+    pub player_two_wins: u64,
+    /// Continuations ending in a draw
+    pub draws: u64,
+}
+
+impl OutcomeCounts {
+    /// Number of continuations, regardless of their result. Same as
+    /// [`TicTacToe::count_complete_games`].
+    pub fn total(&self) -> u64 {
+        self.player_one_wins + self.player_two_wins + self.draws
+    }
+}
+
+impl TicTacToe {
+    /// Counts the finished games reachable from this position by their result. Every sequence of
+    /// legal moves is counted as a game of its own, so a finished position reached by several
+    /// move orders counts several times. Distinct boards are not deduplicated. A position which
+    /// is already finished counts as a single game.
+    pub fn outcome_counts(&self) -> OutcomeCounts {
+        let mut counts = OutcomeCounts::default();
+        match self.state().result() {
+            Some(GameResult::VictoryPlayerOne) => counts.player_one_wins = 1,
+            Some(GameResult::VictoryPlayerTwo) => counts.player_two_wins = 1,
+            Some(GameResult::Draw) => counts.draws = 1,
+            None => {
+                for mov in self.open_fields() {
+                    let mut child = *self;
+                    child.play_move(&mov);
+                    let child = child.outcome_counts();
+                    counts.player_one_wins += child.player_one_wins;
+                    counts.player_two_wins += child.player_two_wins;
+                    counts.draws += child.draws;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Every sequence of legal moves, starting from the empty board, which results in this
+    /// position. Returns an empty `Vec` if the position can not be reached by legal play. The
+    /// number of sequences is bounded by `9!`, but can get large for positions with many stones.
+    pub fn all_move_orders(&self) -> Vec<Vec<CellIndex>> {
+        let mut orders = Vec::new();
+        collect_move_orders(TicTacToe::new(), *self, &mut Vec::new(), &mut orders);
+        orders
+    }
+}
+
+/// Depth first search for all move orders leading from `board` to `target`. `moves` leads from
+/// the empty board to `board`.
+fn collect_move_orders(
+    board: TicTacToe,
+    target: TicTacToe,
+    moves: &mut Vec<CellIndex>,
+    orders: &mut Vec<Vec<CellIndex>>,
+) {
+    if board == target {
+        orders.push(moves.clone());
+        return;
+    }
+    let stone = match board.state() {
+        TicTacToeState::TurnPlayerOne => Cell::PlayerOne,
+        TicTacToeState::TurnPlayerTwo => Cell::PlayerTwo,
+        _ => return,
+    };
+    for mov in board.open_fields() {
+        if target.0.field(mov) != stone {
+            continue;
+        }
+        let mut child = board;
+        child.play_move(&mov);
+        moves.push(mov);
+        collect_move_orders(child, target, moves, orders);
+        moves.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Player;
+
+    use super::*;
+
+    #[test]
+    fn number_of_terminal_positions() {
+        let terminals: Vec<_> = terminal_positions().collect();
+        let count = |state| terminals.iter().filter(|t| t.state() == state).count();
+
+        assert_eq!(terminals.len(), 958);
+        assert_eq!(count(TicTacToeState::VictoryPlayerOne), 626);
+        assert_eq!(count(TicTacToeState::VictoryPlayerTwo), 316);
+        assert_eq!(count(TicTacToeState::Draw), 16);
+    }
+
+    #[test]
+    fn number_of_distinct_positions() {
+        assert_eq!(count_distinct_positions(), 5478);
+        assert_eq!(count_distinct_positions_canonical(), 765);
+    }
+
+    #[test]
+    fn positions_by_ply() {
+        let counts: Vec<_> = (0..=9).map(|n| boards_of_ply(n).count()).collect();
+        assert_eq!(counts, [1, 9, 72, 252, 756, 1260, 1520, 1140, 390, 78]);
+        assert_eq!(counts.iter().sum::<usize>(), count_distinct_positions());
+        assert!(boards_of_ply(5).all(|board| board.current_player() == Player::Two));
+        assert_eq!(boards_of_ply(10).count(), 0);
+    }
+
+    #[test]
+    fn canonical_positions_are_canonical() {
+        let canonical: Vec<_> = all_legal_positions_canonical().collect();
+        assert!(canonical.iter().all(|board| board.canonical() == *board));
+        assert!(canonical.iter().all(TicTacToe::is_legal_position));
+        assert_eq!(canonical[0], TicTacToe::new());
+    }
+
+    #[test]
+    fn every_game_line() {
+        let mut lines = all_game_lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            [0, 1, 2, 3, 4, 5, 6].map(CellIndex::new)
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            [0, 1, 2, 3, 4, 5, 7, 6, 8].map(CellIndex::new)
+        );
+        assert_eq!(
+            2 + lines.count() as u64,
+            TicTacToe::new().count_complete_games()
+        );
+    }
+
+    #[test]
+    fn number_of_distinct_games() {
+        assert_eq!(distinct_games_canonical(), 26830);
+        // The fastest win of the first opening: Player one takes the top row
+        let first = distinct_games_canonical_iter().next().unwrap();
+        assert_eq!(first, [0, 1, 2, 3, 4, 5, 6].map(CellIndex::new));
+    }
+
+    #[test]
+    fn legal_positions_are_exactly_the_reachable_ones() {
+        let reachable: HashSet<_> = reachable_positions().into_iter().collect();
+        let all: Vec<_> = (0..9).fold(vec![TicTacToe::new()], |boards, i| {
+            boards
+                .into_iter()
+                .flat_map(|board| {
+                    [Cell::Empty, Cell::PlayerOne, Cell::PlayerTwo].map(|cell| {
+                        let mut board = board;
+                        board.0.mark_cell(CellIndex(i), cell);
+                        board
+                    })
+                })
+                .collect()
+        });
+        assert_eq!(all.len(), 19683);
+
+        let (legal, illegal) = partition_legal(&all);
+
+        assert_eq!(legal.len(), reachable.len());
+        assert!(legal.iter().all(|board| reachable.contains(board)));
+        assert!(illegal.iter().all(|board| !reachable.contains(board)));
+    }
+
+    #[test]
+    fn partition_preserves_order() {
+        let mut one = TicTacToe::new();
+        one.play_move(&CellIndex::new(4));
+        let mut two = one;
+        two.play_move(&CellIndex::new(0));
+        let mut illegal = TicTacToe::new();
+        illegal.0.mark_cell(CellIndex::new(0), Cell::PlayerTwo);
+
+        let (legal, rejected) = partition_legal(&[two, illegal, one, illegal]);
+
+        assert_eq!(legal, [two, one]);
+        assert_eq!(rejected, [illegal, illegal]);
+    }
+
+    #[test]
+    fn outcome_counts_of_all_games() {
+        let counts = TicTacToe::new().outcome_counts();
+        let expected = OutcomeCounts {
+            player_one_wins: 131184,
+            player_two_wins: 77904,
+            draws: 46080,
+        };
+        assert_eq!(counts, expected);
+        assert_eq!(counts.total(), TicTacToe::new().count_complete_games());
+    }
+
+    #[test]
+    fn move_orders_of_three_stones() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(4));
+        board.play_move(&CellIndex::new(8));
+
+        let orders = board.all_move_orders();
+
+        let expected = vec![
+            vec![CellIndex::new(0), CellIndex::new(4), CellIndex::new(8)],
+            vec![CellIndex::new(8), CellIndex::new(4), CellIndex::new(0)],
+        ];
+        assert_eq!(orders, expected);
+        assert_eq!(TicTacToe::new().all_move_orders(), vec![Vec::new()]);
+    }
+
+    #[test]
+    fn no_move_orders_for_impossible_position() {
+        let mut board = TicTacToe::new();
+        board.0.mark_cell(CellIndex::new(0), Cell::PlayerOne);
+        board.0.mark_cell(CellIndex::new(1), Cell::PlayerOne);
+        assert!(board.all_move_orders().is_empty());
+    }
+
+    #[test]
+    fn no_move_orders_continuing_after_victory() {
+        // Both players have completed a row. Whoever completed it second would have played after
+        // the game has already been finished.
+        let mut board = TicTacToe::new();
+        for (i, cell) in [
+            (0, Cell::PlayerOne),
+            (1, Cell::PlayerOne),
+            (2, Cell::PlayerOne),
+            (3, Cell::PlayerTwo),
+            (4, Cell::PlayerTwo),
+            (5, Cell::PlayerTwo),
+        ] {
+            board.0.mark_cell(CellIndex::new(i), cell);
+        }
+        assert!(board.all_move_orders().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use rand::{rngs::Xoshiro256PlusPlus, seq::IteratorRandom, Rng, SeedableRng};
+
+use crate::{solver::optimal_result, CellIndex, GameResult, TicTacToe};
+
+impl TicTacToe {
+    /// One of the open fields, chosen uniformly at random. `None` if the game is already finished.
+    pub fn random_move<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<CellIndex> {
+        if self.state().is_terminal() {
+            return None;
+        }
+        self.open_fields().choose(rng)
+    }
+}
+
+/// Outcomes of many games with uniformly random moves, see [`random_game_stats`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GameStats {
+    /// Fraction of games won by player one
+    pub player_one_wins: f64,
+    /// Fraction of games won by player two
+    pub player_two_wins: f64,
+    /// Fraction of games ending in a draw
+    pub draws: f64,
+    /// Average number of moves played per game
+    pub average_length: f64,
+}
+
+/// Plays `games` games in which both players pick uniformly random moves and summarizes their
+/// outcomes. Results are reproducible if `rng` is seeded. All fields are `NaN` if `games` is zero.
+pub fn random_game_stats<R: Rng + ?Sized>(games: usize, rng: &mut R) -> GameStats {
+    let (mut one, mut two, mut draws, mut moves) = (0, 0, 0, 0);
+    for _ in 0..games {
+        let mut board = TicTacToe::new();
+        let result = board
+            .play_until_terminal(|board| board.random_move(rng).unwrap())
+            .unwrap();
+        match result {
+            GameResult::VictoryPlayerOne => one += 1,
+            GameResult::VictoryPlayerTwo => two += 1,
+            GameResult::Draw => draws += 1,
+        }
+        moves += board.0.stones() as usize;
+    }
+    let games = games as f64;
+    GameStats {
+        player_one_wins: one as f64 / games,
+        player_two_wins: two as f64 / games,
+        draws: draws as f64 / games,
+        average_length: moves as f64 / games,
+    }
+}
+
+/// Training data from `games` games in which both players pick uniformly random moves, see
+/// [`TicTacToe::random_move`]. For every move played there is one sample holding the position
+/// before the move, the move and the result the game eventually ended with. Samples are ordered
+/// by game and within each game by ply, so a game of `n` moves contributes `n` consecutive
+/// samples. The data only depends on the state of `rng`, so it is reproducible if `rng` is seeded.
+pub fn generate_selfplay<R: Rng + ?Sized>(
+    games: usize,
+    rng: &mut R,
+) -> Vec<(TicTacToe, CellIndex, GameResult)> {
+    let mut samples = Vec::new();
+    for _ in 0..games {
+        let mut board = TicTacToe::new();
+        let mut game = Vec::new();
+        let result = board
+            .play_until_terminal(|board| {
+                let mov = board.random_move(rng).unwrap();
+                game.push((*board, mov));
+                mov
+            })
+            .unwrap();
+        samples.extend(game.into_iter().map(|(board, mov)| (board, mov, result)));
+    }
+    samples
+}
+
+/// Outcome of Tic Tac Toe under perfect play compared to random play, see [`outcome_summary`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OutcomeSummary {
+    /// Result if both players play perfectly. Computed exactly by the solver.
+    pub optimal: GameResult,
+    /// Estimated outcome probabilities if both players pick uniformly random moves.
+    pub random: GameStats,
+}
+
+/// Contrasts the outcome of Tic Tac Toe under perfect play (a draw) with the outcome of
+/// `random_games` games of random play. The random games are driven by a generator seeded with
+/// `seed`, so the summary is reproducible across runs and platforms.
+pub fn outcome_summary(random_games: usize, seed: u64) -> OutcomeSummary {
+    let optimal = optimal_result(&TicTacToe::new());
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    OutcomeSummary {
+        optimal,
+        random: random_game_stats(random_games, &mut rng),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn random_move_picks_open_field() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut game = TicTacToe::new();
+        while let Some(mov) = game.random_move(&mut rng) {
+            assert!(game.owner(mov).is_none());
+            game.play_move(&mov);
+        }
+        assert!(game.state().is_terminal());
+    }
+
+    #[test]
+    fn first_mover_advantage_in_random_games() {
+        let stats = random_game_stats(2000, &mut StdRng::seed_from_u64(42));
+
+        // Exact probabilities are roughly 58.5%, 28.8% and 12.7%
+        assert!((stats.player_one_wins - 0.585).abs() < 0.05);
+        assert!((stats.player_two_wins - 0.288).abs() < 0.05);
+        assert!((stats.draws - 0.127).abs() < 0.05);
+        assert!(stats.average_length > 5.0 && stats.average_length < 9.0);
+
+        let again = random_game_stats(2000, &mut StdRng::seed_from_u64(42));
+        assert_eq!(stats, again);
+    }
+
+    #[test]
+    fn reproducible_selfplay_data() {
+        let samples = generate_selfplay(20, &mut StdRng::seed_from_u64(42));
+        assert_eq!(
+            samples,
+            generate_selfplay(20, &mut StdRng::seed_from_u64(42))
+        );
+
+        let games = samples
+            .iter()
+            .filter(|(board, _, _)| board.is_empty())
+            .count();
+        assert_eq!(games, 20);
+        for window in samples.windows(2) {
+            let (board, mov, result) = window[0];
+            let (next, _, next_result) = window[1];
+            let mut after = board;
+            after.play_move(&mov);
+            if next.is_empty() {
+                assert_eq!(after.state().result(), Some(result));
+            } else {
+                assert_eq!(after, next);
+                assert_eq!(result, next_result);
+            }
+        }
+    }
+
+    #[test]
+    fn summary_of_optimal_and_random_play() {
+        let summary = outcome_summary(1000, 7);
+        assert_eq!(summary.optimal, GameResult::Draw);
+        assert!(summary.random.player_one_wins > summary.random.player_two_wins);
+        assert_eq!(summary, outcome_summary(1000, 7));
+    }
+}
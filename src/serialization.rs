@@ -0,0 +1,173 @@
+//! Support for `serde`. A [`Game`] is stored as its list of moves, so deserializing it restores
+//! the position as well as the ability to undo and redo moves. A [`TicTacToe`] board is stored as a
+//! readable grid for human readable formats and as its ternary id otherwise.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CellIndex, Game, GridConfig, TicTacToe};
+
+/// For human readable formats like JSON, serialized as the nine characters of
+/// [`TicTacToe::to_compact`], e.g. `"X...O...."`.
+/// For other formats serialized as the number returned by [`TicTacToe::ternary_id`].
+impl Serialize for TicTacToe {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_compact())
+        } else {
+            serializer.serialize_u16(self.ternary_id())
+        }
+    }
+}
+
+/// Each kind of format reads the form [`Serialize`] writes for it. Human readable formats are
+/// self-describing and accept both the grid and the ternary id. Other formats, e.g. bincode, can
+/// only read the ternary id. Grids are parsed by [`TicTacToe::from_grid`] with the default config.
+/// Fails for illegal positions.
+impl<'de> Deserialize<'de> for TicTacToe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BoardVisitor)
+        } else {
+            deserializer.deserialize_u16(BoardVisitor)
+        }
+    }
+}
+
+struct BoardVisitor;
+
+impl de::Visitor<'_> for BoardVisitor {
+    type Value = TicTacToe;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a grid of nine cells or a ternary id of a legal tic tac toe position")
+    }
+
+    fn visit_str<E: de::Error>(self, grid: &str) -> Result<TicTacToe, E> {
+        TicTacToe::from_grid(grid, GridConfig::default()).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, id: u64) -> Result<TicTacToe, E> {
+        let id =
+            u16::try_from(id).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(id), &self))?;
+        TicTacToe::from_ternary_id(id).map_err(E::custom)
+    }
+}
+
+/// Serialized as the index of the cell, i.e. a number from 0 to 8.
+impl Serialize for CellIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CellIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = u8::deserialize(deserializer)?;
+        if index >= 9 {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(index.into()),
+                &"a cell index from 0 to 8",
+            ));
+        }
+        Ok(CellIndex(index))
+    }
+}
+
+/// Representation of a [`Game`] on the wire.
+#[derive(Serialize, Deserialize)]
+struct GameRecord {
+    /// All recorded moves, including the ones which have been taken back and could be redone.
+    moves: Vec<CellIndex>,
+    /// Number of moves on the board
+    ply: usize,
+}
+
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GameRecord {
+            moves: self.recorded_moves().to_vec(),
+            ply: self.moves().len(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fails unless the moves form a legal game and `ply` does not exceed their number.
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GameRecord::deserialize(deserializer)?;
+        let mut game = Game::new();
+        for (n, &mov) in record.moves.iter().enumerate() {
+            game.try_play_move(mov)
+                .map_err(|e| de::Error::custom(format!("Illegal move {}: {}", n, e)))?;
+        }
+        game.goto_ply(record.ply).map_err(de::Error::custom)?;
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Player;
+
+    use super::*;
+
+    #[test]
+    fn game_round_trip_keeps_history() {
+        let mut game = Game::new();
+        for i in [4, 0, 8] {
+            game.play_move(&CellIndex::new(i));
+        }
+        game.undo();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, r#"{"moves":[4,0,8],"ply":2}"#);
+
+        let mut restored: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, game);
+        assert_eq!(restored.redo(), Some(CellIndex::new(8)));
+        restored.undo();
+        assert_eq!(restored.undo(), Some((CellIndex::new(0), Player::Two)));
+    }
+
+    #[test]
+    fn board_round_trip_for_both_kinds_of_formats() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(4));
+
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(json, r#""X...O....""#);
+        assert_eq!(serde_json::from_str::<TicTacToe>(&json).unwrap(), board);
+        // The ternary id is accepted by human readable formats, too
+        let id = board.ternary_id().to_string();
+        assert_eq!(serde_json::from_str::<TicTacToe>(&id).unwrap(), board);
+
+        let bytes = bincode::serde::encode_to_vec(board, bincode::config::standard()).unwrap();
+        // Ternary id 163 is small enough for bincode to encode it as a single byte
+        assert_eq!(bytes, [163]);
+        let (decoded, _): (TicTacToe, _) =
+            bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+        assert_eq!(decoded, board);
+    }
+
+    #[test]
+    fn reject_illegal_board() {
+        assert!(serde_json::from_str::<TicTacToe>(r#""XX.......""#).is_err());
+        assert!(serde_json::from_str::<TicTacToe>(r#""X..""#).is_err());
+        assert!(serde_json::from_str::<TicTacToe>("19683").is_err());
+    }
+
+    #[test]
+    fn reject_illegal_game() {
+        // Cell 4 played twice
+        assert!(serde_json::from_str::<Game>(r#"{"moves":[4,0,4],"ply":3}"#).is_err());
+        // Cell index out of range
+        assert!(serde_json::from_str::<Game>(r#"{"moves":[9],"ply":1}"#).is_err());
+        // More plies than moves
+        assert!(serde_json::from_str::<Game>(r#"{"moves":[4],"ply":2}"#).is_err());
+    }
+}
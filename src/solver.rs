@@ -0,0 +1,495 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{CellIndex, GameResult, Player, TicTacToe};
+
+/// Result of [`TicTacToe::analyze`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Analysis {
+    /// Same as [`TicTacToe::best_move`]. `None` if the game is already finished.
+    pub best_move: Option<CellIndex>,
+    /// Same as [`TicTacToe::evaluate`], i.e. the actual outcome for finished games.
+    pub value: i32,
+    /// Same as [`TicTacToe::best_moves`]. Empty if the game is already finished.
+    pub best_moves: Vec<CellIndex>,
+    /// The moves played if both sides keep playing perfectly until the game is finished. Starts
+    /// with `best_move`. Among equally good moves the one with the lowest index is played at
+    /// each ply, just like [`TicTacToe::best_move`] would.
+    pub principal_variation: Vec<CellIndex>,
+}
+
+impl TicTacToe {
+    /// Value of the position from the perspective of the player to move, assuming perfect play by
+    /// both sides. `1` if the player to move can force a victory, `-1` if the opponent can force a
+    /// victory and `0` if the game ends in a draw. For finished games this is the actual outcome,
+    /// i.e. `-1` for won positions, since the loser would be next to move.
+    ///
+    /// Works for any position accepted by [`Self::is_legal_position`], whether it has been played
+    /// or set up, e.g. with [`Self::from_cells_with_turn`]. Boards do not store whose turn it is:
+    /// `from_cells_with_turn` only checks its `to_move` against the stone counts, which in legal
+    /// positions always determine the player to move. So the solver derives it from them, and no
+    /// legal position exists for which this picks the wrong side.
+    pub fn evaluate(&self) -> i32 {
+        negamax(*self, &mut HashMap::new()).signum()
+    }
+
+    /// A move which leads to the best outcome for the player to move, assuming perfect play by both
+    /// sides. Among winning moves the fastest win is preferred, among losing moves the slowest
+    /// loss. If several moves are still equally good, the one with the lowest index is chosen.
+    /// `None` if the game is already finished.
+    pub fn best_move(&self) -> Option<CellIndex> {
+        self.best_move_by(|a, b| a.0.cmp(&b.0))
+    }
+
+    /// Plays [`Self::best_move`] and returns it. Leaves the board untouched and returns `None` if
+    /// the game is already finished.
+    pub fn play_best(&mut self) -> Option<CellIndex> {
+        let mov = self.best_move()?;
+        self.play_move(&mov);
+        Some(mov)
+    }
+
+    /// Like [`Self::best_move`], but breaks ties between equally good moves using `tiebreak`. The
+    /// move which compares as the smallest is chosen, e.g. `|a, b| a.row().cmp(&b.row())` prefers
+    /// moves in the top row.
+    pub fn best_move_by(
+        &self,
+        tiebreak: impl Fn(CellIndex, CellIndex) -> Ordering,
+    ) -> Option<CellIndex> {
+        self.best_moves()
+            .into_iter()
+            .min_by(|&a, &b| tiebreak(a, b))
+    }
+
+    /// All moves which are as good as [`Self::best_move`], in ascending order. Empty if the game is
+    /// already finished.
+    pub fn best_moves(&self) -> Vec<CellIndex> {
+        optimal_moves(self, &mut HashMap::new())
+    }
+
+    /// Every move which loses against perfect play of the opponent, in ascending order. Empty if no
+    /// move loses or the game is already finished.
+    pub fn losing_moves(&self) -> Vec<CellIndex> {
+        self.move_values()
+            .into_iter()
+            .filter(|&(_, value)| value < 0)
+            .map(|(mov, _)| mov)
+            .collect()
+    }
+
+    /// Each legal move with the minimax value of the resulting position from the perspective of the
+    /// player making it, in ascending order of the moves.
+    fn move_values(&self) -> Vec<(CellIndex, i32)> {
+        self.move_values_memo(&mut HashMap::new())
+    }
+
+    /// Same as [`Self::move_values`], but shares `memo` with other searches.
+    fn move_values_memo(&self, memo: &mut HashMap<TicTacToe, i32>) -> Vec<(CellIndex, i32)> {
+        if self.state().is_terminal() {
+            return Vec::new();
+        }
+        self.open_fields()
+            .map(|mov| {
+                let mut child = *self;
+                child.play_move(&mov);
+                (mov, -negamax(child, memo))
+            })
+            .collect()
+    }
+
+    /// Everything the solver knows about this position, computed with a single search. See
+    /// [`Analysis`].
+    pub fn analyze(&self) -> Analysis {
+        let mut memo = HashMap::new();
+        let value = negamax(*self, &mut memo).signum();
+        let best_moves = optimal_moves(self, &mut memo);
+        let mut principal_variation = Vec::new();
+        let mut board = *self;
+        while let Some(&mov) = optimal_moves(&board, &mut memo).first() {
+            board.play_move(&mov);
+            principal_variation.push(mov);
+        }
+        Analysis {
+            best_move: best_moves.first().copied(),
+            value,
+            best_moves,
+            principal_variation,
+        }
+    }
+
+    /// Number of nodes visited by a plain minimax search of the complete game tree, without
+    /// memoization or pruning. Every position along every line of play counts as a node, including
+    /// this one and finished games, which are not expanded further. Positions reached by several
+    /// move orders count once per move order. From the empty board this is 549946.
+    pub fn search_nodes(&self) -> u64 {
+        if self.state().is_terminal() {
+            return 1;
+        }
+        1 + self
+            .open_fields()
+            .map(|mov| {
+                let mut child = *self;
+                child.play_move(&mov);
+                child.search_nodes()
+            })
+            .sum::<u64>()
+    }
+
+    /// `true` if the player to move has at least one move which does not lose against perfect
+    /// play of the opponent. `false` if every move loses, or if the game is already finished.
+    pub fn can_hold_draw(&self) -> bool {
+        !self.state().is_terminal() && self.evaluate() >= 0
+    }
+
+    /// `true` if the result of the game no longer depends on the moves played, i.e. the best and
+    /// the worst outcome for either player are the same. This is the case for finished games, but
+    /// also e.g. if every remaining line of play ends in a draw. Note that under perfect play the
+    /// result is always known, see [`Self::evaluate`]. This asks whether even mistakes can still
+    /// change it.
+    pub fn is_decided(&self) -> bool {
+        reachable_results(*self, &mut HashMap::new()).count_ones() == 1
+    }
+
+    /// The only move which does not lose against perfect play, if the player to move has exactly
+    /// one such move. `None` if several moves hold, if every move loses, or if the game is already
+    /// finished.
+    pub fn forced_move(&self) -> Option<CellIndex> {
+        let mut safe = self
+            .move_values()
+            .into_iter()
+            .filter(|&(_, value)| value >= 0)
+            .map(|(mov, _)| mov);
+        match (safe.next(), safe.next()) {
+            (Some(mov), None) => Some(mov),
+            _ => None,
+        }
+    }
+
+    /// Value of the position from the perspective of `player`, assuming perfect play by both
+    /// sides. Same scale as [`Self::evaluate`], i.e. `1` if `player` can force a victory, `-1` if
+    /// the opponent can, and `0` for a draw.
+    pub fn score_for(&self, player: Player) -> i32 {
+        let value = self.evaluate();
+        if player == self.current_player() {
+            value
+        } else {
+            -value
+        }
+    }
+}
+
+/// Bit for each result which can be reached from `board` by any sequence of legal moves, see
+/// [`TicTacToe::is_decided`]. Positions already visited are looked up in `memo`.
+fn reachable_results(board: TicTacToe, memo: &mut HashMap<TicTacToe, u8>) -> u8 {
+    if let Some(&results) = memo.get(&board) {
+        return results;
+    }
+    let results = match board.state().result() {
+        Some(result) => 1 << result as u8,
+        None => board
+            .open_fields()
+            .map(|mov| {
+                let mut child = board;
+                child.play_move(&mov);
+                reachable_results(child, memo)
+            })
+            .fold(0, |results, child| results | child),
+    };
+    memo.insert(board, results);
+    results
+}
+
+/// Moves of [`TicTacToe::best_moves`], sharing `memo` with other searches.
+fn optimal_moves(board: &TicTacToe, memo: &mut HashMap<TicTacToe, i32>) -> Vec<CellIndex> {
+    let values = board.move_values_memo(memo);
+    let best = values.iter().map(|&(_, value)| value).max();
+    values
+        .into_iter()
+        .filter(|&(_, value)| Some(value) == best)
+        .map(|(mov, _)| mov)
+        .collect()
+}
+
+/// Result of the game if both players play perfectly from `board` on.
+pub(crate) fn optimal_result(board: &TicTacToe) -> GameResult {
+    match board.score_for(Player::One) {
+        1 => GameResult::VictoryPlayerOne,
+        -1 => GameResult::VictoryPlayerTwo,
+        _ => GameResult::Draw,
+    }
+}
+
+/// For each of the nine opening moves of player one, the result of the game if both players play
+/// perfectly afterwards. All of them are draws. Ordered by ascending cell index.
+pub fn opening_outcomes() -> Vec<(CellIndex, GameResult)> {
+    TicTacToe::new()
+        .open_fields()
+        .map(|mov| {
+            let mut board = TicTacToe::new();
+            board.play_move(&mov);
+            (mov, optimal_result(&board))
+        })
+        .collect()
+}
+
+/// For each cell, the value of opening the game there from the perspective of player one,
+/// assuming perfect play afterwards. On the scale of [`TicTacToe::score_for`], so every entry is
+/// `0`: No opening move wins or loses.
+pub fn first_move_value_map() -> [i32; 9] {
+    [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex(i));
+        board.score_for(Player::One)
+    })
+}
+
+/// Like [`opening_outcomes`], but only for the three openings which are distinct up to rotation
+/// and reflection of the board: Corner (0), edge (1) and center (4).
+pub fn distinct_opening_outcomes() -> Vec<(CellIndex, GameResult)> {
+    let mut seen = HashSet::new();
+    opening_outcomes()
+        .into_iter()
+        .filter(|&(mov, _)| {
+            let mut board = TicTacToe::new();
+            board.play_move(&mov);
+            seen.insert(board.canonical())
+        })
+        .collect()
+}
+
+/// Minimax value for the player to move. Positive if the player to move wins, negative if the
+/// opponent wins and zero for a draw. The magnitude is the number of empty cells left at the end
+/// of the game plus one, so faster wins are worth more. Positions already visited are looked up in
+/// `memo`.
+pub(crate) fn negamax(board: TicTacToe, memo: &mut HashMap<TicTacToe, i32>) -> i32 {
+    if let Some(&value) = memo.get(&board) {
+        return value;
+    }
+    let value = if board.0.victory() {
+        -(10 - i32::from(board.0.stones()))
+    } else {
+        board
+            .open_fields()
+            .map(|mov| {
+                let mut child = board;
+                child.play_move(&mov);
+                -negamax(child, memo)
+            })
+            .max()
+            // No open fields and no victory: Draw
+            .unwrap_or(0)
+    };
+    memo.insert(board, value);
+    value
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{Cell, TicTacToeState};
+
+    use super::*;
+
+    #[test]
+    fn empty_board_is_a_draw() {
+        assert_eq!(TicTacToe::new().evaluate(), 0);
+    }
+
+    #[test]
+    fn all_openings_are_draws() {
+        let outcomes = opening_outcomes();
+        assert_eq!(outcomes.len(), 9);
+        assert!(outcomes
+            .iter()
+            .all(|&(_, result)| result == GameResult::Draw));
+
+        let distinct = distinct_opening_outcomes();
+        let expected = [0, 1, 4].map(|i| (CellIndex::new(i), GameResult::Draw));
+        assert_eq!(distinct, expected);
+    }
+
+    #[test]
+    fn every_opening_move_is_worth_nothing() {
+        assert_eq!(first_move_value_map(), [0; 9]);
+    }
+
+    #[test]
+    fn best_move_completes_line() {
+        let mut game = TicTacToe::new();
+        for i in [4, 0, 2, 1] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(game.best_move(), Some(CellIndex::new(6)));
+        game.play_move(&CellIndex::new(6));
+        assert_eq!(game.best_move(), None);
+    }
+
+    #[test]
+    fn perfect_play_draws() {
+        let mut game = TicTacToe::new();
+        while game.play_best().is_some() {}
+        assert_eq!(game.state(), TicTacToeState::Draw);
+        let end = game;
+        assert_eq!(game.play_best(), None);
+        assert_eq!(game, end);
+    }
+
+    #[test]
+    fn tie_breaking_between_optimal_moves() {
+        let board = TicTacToe::new();
+        // Every opening move draws
+        assert_eq!(board.best_moves().len(), 9);
+        assert_eq!(board.best_move(), Some(CellIndex::new(0)));
+        let center_first = |a: CellIndex, b: CellIndex| (b.0 == 4).cmp(&(a.0 == 4));
+        assert_eq!(board.best_move_by(center_first), Some(CellIndex::new(4)));
+        let highest_index = |a: CellIndex, b: CellIndex| b.0.cmp(&a.0);
+        assert_eq!(board.best_move_by(highest_index), Some(CellIndex::new(8)));
+
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(0));
+        // Only the center does not lose
+        assert_eq!(game.best_move_by(highest_index), Some(CellIndex::new(4)));
+    }
+
+    #[test]
+    fn losing_replies_to_corner_opening() {
+        let mut game = TicTacToe::new();
+        assert!(game.losing_moves().is_empty());
+        game.play_move(&CellIndex::new(0));
+        // Everything but the center loses
+        let expected = [1, 2, 3, 5, 6, 7, 8].map(CellIndex::new);
+        assert_eq!(game.losing_moves(), expected);
+    }
+
+    #[test]
+    fn analysis_in_one_pass() {
+        let mut game = TicTacToe::new();
+        for i in [4, 0, 2, 1] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let analysis = game.analyze();
+        assert_eq!(analysis.best_move, Some(CellIndex::new(6)));
+        assert_eq!(analysis.value, 1);
+        assert_eq!(analysis.best_moves, [CellIndex::new(6)]);
+        assert_eq!(analysis.principal_variation, [CellIndex::new(6)]);
+
+        let analysis = TicTacToe::new().analyze();
+        assert_eq!(analysis.value, 0);
+        assert_eq!(analysis.best_moves.len(), 9);
+        let mut end = TicTacToe::new();
+        for mov in &analysis.principal_variation {
+            end.play_move(mov);
+        }
+        assert_eq!(end.state(), TicTacToeState::Draw);
+    }
+
+    #[test]
+    fn analysis_of_finished_game() {
+        let mut game = TicTacToe::new();
+        for i in [4, 0, 2, 1, 6] {
+            game.play_move(&CellIndex::new(i));
+        }
+        let analysis = game.analyze();
+        assert_eq!(analysis.best_move, None);
+        assert_eq!(analysis.value, -1);
+        assert!(analysis.best_moves.is_empty());
+        assert!(analysis.principal_variation.is_empty());
+    }
+
+    #[test]
+    fn size_of_game_tree() {
+        assert_eq!(TicTacToe::new().search_nodes(), 549946);
+        let mut game = TicTacToe::new();
+        for i in [0, 1, 2, 4, 3, 5, 7, 6] {
+            game.play_move(&CellIndex::new(i));
+        }
+        // The root and the full board after the only remaining move
+        assert_eq!(game.search_nodes(), 2);
+    }
+
+    #[test]
+    fn hold_draw() {
+        let mut game = TicTacToe::new();
+        assert!(game.can_hold_draw());
+        game.play_move(&CellIndex::new(0));
+        // Only the center holds, but that is enough
+        assert!(game.can_hold_draw());
+        game.play_move(&CellIndex::new(1));
+        assert!(game.can_hold_draw());
+        game.play_move(&CellIndex::new(4));
+        // Player one threatens 8 and can fork next move
+        assert!(!game.can_hold_draw());
+    }
+
+    #[test]
+    fn only_move() {
+        let mut game = TicTacToe::new();
+        assert_eq!(game.forced_move(), None);
+        game.play_move(&CellIndex::new(0));
+        // Only the center does not lose against a corner opening
+        assert_eq!(game.forced_move(), Some(CellIndex::new(4)));
+        game.play_move(&CellIndex::new(1));
+        game.play_move(&CellIndex::new(4));
+        // Every move of player two loses
+        assert_eq!(game.forced_move(), None);
+    }
+
+    #[test]
+    fn decided_positions() {
+        let mut game = TicTacToe::new();
+        assert!(!game.is_decided());
+        for i in [0, 1, 2, 4, 3, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+        // -------
+        // |X|O|X|
+        // |-----|
+        // |X|O|O|
+        // |-----|
+        // | |X| |
+        // -------
+        // Player two draws by blocking 6, but loses by playing 8
+        assert!(!game.is_decided());
+        game.play_move(&CellIndex::new(8));
+        // Player one is left with the winning move on 6
+        assert!(game.is_decided());
+        game.play_move(&CellIndex::new(6));
+        assert!(game.is_decided());
+    }
+
+    #[test]
+    fn solve_set_up_positions() {
+        // Covers `evaluate` and `best_move`. There is no `classify` to test. Positions with equal
+        // stone counts and player two to move are illegal, so they are rejected on setup.
+        use Cell::{Empty as E, PlayerOne as X, PlayerTwo as O};
+
+        // Equal stone counts, so player one is to move and wins on 2. Had player two been to move,
+        // they would have won on 5 instead.
+        let cells = [X, X, E, O, O, E, E, E, E];
+        let board = TicTacToe::from_cells_with_turn(cells, Player::One).unwrap();
+        assert_eq!(board.evaluate(), 1);
+        assert_eq!(board.best_move(), Some(CellIndex::new(2)));
+        assert!(TicTacToe::from_cells_with_turn(cells, Player::Two).is_err());
+
+        // Player one has a stone more, so player two is to move and must block 8
+        let cells = [X, O, E, E, X, E, E, E, E];
+        let board = TicTacToe::from_cells_with_turn(cells, Player::Two).unwrap();
+        assert_eq!(board.best_move(), Some(CellIndex::new(8)));
+        assert_eq!(board.score_for(Player::One), 1);
+    }
+
+    #[test]
+    fn score_from_fixed_perspective() {
+        // Player one takes a corner, player two answers with an edge and loses
+        let mut game = TicTacToe::new();
+        game.play_move(&CellIndex::new(0));
+        game.play_move(&CellIndex::new(1));
+
+        assert_eq!(game.current_player(), Player::One);
+        assert_eq!(game.evaluate(), 1);
+        assert_eq!(game.score_for(Player::One), 1);
+        assert_eq!(game.score_for(Player::Two), -1);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{CellIndex, GameResult, Player, TicTacToe};
+
+/// A way of choosing moves, e.g. a bot or a human player behind a user interface.
+pub trait Strategy {
+    /// Picks the next move for the player to move on `board`. Is only called for games which
+    /// are not finished yet.
+    fn choose_move(&mut self, board: &TicTacToe) -> CellIndex;
+}
+
+/// Plays perfectly using [`TicTacToe::best_move`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MinimaxStrategy;
+
+impl Strategy for MinimaxStrategy {
+    fn choose_move(&mut self, board: &TicTacToe) -> CellIndex {
+        board
+            .best_move()
+            .expect("Strategy must only be asked for moves in ongoing games.")
+    }
+}
+
+/// Plays rules of thumb using [`TicTacToe::heuristic_move`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct HeuristicStrategy;
+
+impl Strategy for HeuristicStrategy {
+    fn choose_move(&mut self, board: &TicTacToe) -> CellIndex {
+        board
+            .heuristic_move()
+            .expect("Strategy must only be asked for moves in ongoing games.")
+    }
+}
+
+/// Plays uniformly random moves using [`TicTacToe::random_move`].
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct RandomStrategy<R> {
+    /// Source of randomness
+    pub rng: R,
+}
+
+#[cfg(feature = "rand")]
+impl<R: rand::Rng> Strategy for RandomStrategy<R> {
+    fn choose_move(&mut self, board: &TicTacToe) -> CellIndex {
+        board
+            .random_move(&mut self.rng)
+            .expect("Strategy must only be asked for moves in ongoing games.")
+    }
+}
+
+/// Tally of a match between two strategies, see [`run_tournament`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TournamentResult {
+    /// Games won by the first strategy passed to [`run_tournament`]
+    pub wins_a: usize,
+    /// Games won by the second strategy passed to [`run_tournament`]
+    pub wins_b: usize,
+    /// Games ending in a draw
+    pub draws: usize,
+}
+
+impl TournamentResult {
+    /// Total number of games played
+    pub fn games(&self) -> usize {
+        self.wins_a + self.wins_b + self.draws
+    }
+}
+
+/// Plays a full game from the empty board. `first` moves as player one, `second` as player two.
+/// Panics if either strategy picks an illegal move.
+pub fn play_game(first: &mut impl Strategy, second: &mut impl Strategy) -> GameResult {
+    TicTacToe::new()
+        .play_until_terminal(|board| match board.current_player() {
+            Player::One => first.choose_move(board),
+            Player::Two => second.choose_move(board),
+        })
+        .expect("Strategy chose an illegal move.")
+}
+
+/// Plays `games` games between `a` and `b` and counts the results. `a` moves first in the first
+/// game, after which the strategies take turns in moving first. For an even number of games both
+/// moved first equally often. Panics if either strategy picks an illegal move.
+pub fn run_tournament(
+    a: &mut impl Strategy,
+    b: &mut impl Strategy,
+    games: usize,
+) -> TournamentResult {
+    let mut tally = TournamentResult::default();
+    for game in 0..games {
+        let a_moves_first = game % 2 == 0;
+        let result = if a_moves_first {
+            play_game(a, b)
+        } else {
+            play_game(b, a)
+        };
+        match (result, a_moves_first) {
+            (GameResult::Draw, _) => tally.draws += 1,
+            (GameResult::VictoryPlayerOne, true) | (GameResult::VictoryPlayerTwo, false) => {
+                tally.wins_a += 1
+            }
+            (GameResult::VictoryPlayerOne, false) | (GameResult::VictoryPlayerTwo, true) => {
+                tally.wins_b += 1
+            }
+        }
+    }
+    tally
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn minimax_against_minimax_is_a_draw() {
+        let result = play_game(&mut MinimaxStrategy, &mut MinimaxStrategy);
+        assert_eq!(result, GameResult::Draw);
+    }
+
+    /// Always plays the open field with the lowest index.
+    struct LowestIndex;
+
+    impl Strategy for LowestIndex {
+        fn choose_move(&mut self, board: &TicTacToe) -> CellIndex {
+            board.open_fields().next().unwrap()
+        }
+    }
+
+    #[test]
+    fn tournament_alternates_first_move() {
+        // Lowest index against itself always ends with a victory for player one, so each side wins
+        // the games it moves first in.
+        let tally = run_tournament(&mut LowestIndex, &mut LowestIndex, 5);
+        assert_eq!(
+            tally,
+            TournamentResult {
+                wins_a: 3,
+                wins_b: 2,
+                draws: 0
+            }
+        );
+        assert_eq!(tally.games(), 5);
+    }
+
+    #[test]
+    fn minimax_never_loses_against_heuristic() {
+        let tally = run_tournament(&mut MinimaxStrategy, &mut HeuristicStrategy, 2);
+        assert_eq!(tally.wins_b, 0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn minimax_never_loses_against_random() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut random = RandomStrategy {
+            rng: StdRng::seed_from_u64(7),
+        };
+        let tally = run_tournament(&mut MinimaxStrategy, &mut random, 20);
+        assert_eq!(tally.wins_b, 0);
+    }
+}
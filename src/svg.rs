@@ -0,0 +1,144 @@
+use std::fmt::Write;
+
+use crate::{Cell, CellIndex, TicTacToe};
+
+/// Controls the output of [`TicTacToe::to_svg_with_config`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SvgConfig {
+    /// Width and height of the image in pixels. The drawing is scaled to fit.
+    pub size: u32,
+    /// If `true` and a player has won, the winning line is struck through.
+    pub highlight_winning_line: bool,
+}
+
+impl Default for SvgConfig {
+    /// 300 by 300 pixels, with the winning line highlighted.
+    fn default() -> SvgConfig {
+        SvgConfig {
+            size: 300,
+            highlight_winning_line: true,
+        }
+    }
+}
+
+/// Width and height of a cell in the coordinate system of the `viewBox`.
+const CELL: u32 = 100;
+/// Distance between the border of a cell and the glyph drawn into it.
+const MARGIN: u32 = 20;
+
+impl TicTacToe {
+    /// Renders the board as a self contained SVG image, using [`SvgConfig::default`].
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_config(&SvgConfig::default())
+    }
+
+    /// Renders the board as a self contained SVG image. Stones of player one are drawn as crosses,
+    /// stones of player two as circles.
+    pub fn to_svg_with_config(&self, config: &SvgConfig) -> String {
+        let full = 3 * CELL;
+        let mut svg = String::new();
+        // Writing to a `String` never fails, so we ignore the results.
+        let _ = write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+             viewBox=\"0 0 {full} {full}\">",
+            size = config.size
+        );
+        let _ = write!(
+            svg,
+            "<g stroke=\"black\" stroke-width=\"4\">\
+             <line x1=\"{c1}\" y1=\"0\" x2=\"{c1}\" y2=\"{full}\"/>\
+             <line x1=\"{c2}\" y1=\"0\" x2=\"{c2}\" y2=\"{full}\"/>\
+             <line x1=\"0\" y1=\"{c1}\" x2=\"{full}\" y2=\"{c1}\"/>\
+             <line x1=\"0\" y1=\"{c2}\" x2=\"{full}\" y2=\"{c2}\"/>\
+             </g>",
+            c1 = CELL,
+            c2 = 2 * CELL
+        );
+        for index in (0..9).map(CellIndex) {
+            let left = u32::from(index.column()) * CELL;
+            let top = u32::from(index.row()) * CELL;
+            match self.0.field(index) {
+                Cell::Empty => (),
+                Cell::PlayerOne => {
+                    let (x1, y1) = (left + MARGIN, top + MARGIN);
+                    let (x2, y2) = (left + CELL - MARGIN, top + CELL - MARGIN);
+                    let _ = write!(
+                        svg,
+                        "<path d=\"M {x1} {y1} L {x2} {y2} M {x2} {y1} L {x1} {y2}\" \
+                         stroke=\"black\" stroke-width=\"8\"/>"
+                    );
+                }
+                Cell::PlayerTwo => {
+                    let _ = write!(
+                        svg,
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"black\" \
+                         stroke-width=\"8\"/>",
+                        left + CELL / 2,
+                        top + CELL / 2,
+                        CELL / 2 - MARGIN
+                    );
+                }
+            }
+        }
+        if config.highlight_winning_line {
+            if let Some([first, _, last]) = self.winning_line() {
+                let center = |i: CellIndex| {
+                    (
+                        u32::from(i.column()) * CELL + CELL / 2,
+                        u32::from(i.row()) * CELL + CELL / 2,
+                    )
+                };
+                let ((x1, y1), (x2, y2)) = (center(first), center(last));
+                let _ = write!(
+                    svg,
+                    "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"red\" \
+                     stroke-width=\"10\" stroke-linecap=\"round\"/>"
+                );
+            }
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn svg_of_won_game() {
+        let mut game = TicTacToe::new();
+        for i in [4, 6, 2, 8, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+
+        let svg = game.to_svg_with_config(&SvgConfig {
+            size: 150,
+            ..SvgConfig::default()
+        });
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"150\""));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert_eq!(svg.matches("<circle").count(), 3);
+        // Winning line from center of cell 6 to center of cell 8
+        assert!(svg.contains("<line x1=\"50\" y1=\"250\" x2=\"250\" y2=\"250\" stroke=\"red\""));
+    }
+
+    #[test]
+    fn svg_without_highlight() {
+        let mut game = TicTacToe::new();
+        for i in [4, 6, 2, 8, 5, 7] {
+            game.play_move(&CellIndex::new(i));
+        }
+
+        let svg = game.to_svg_with_config(&SvgConfig {
+            highlight_winning_line: false,
+            ..SvgConfig::default()
+        });
+
+        assert!(!svg.contains("red"));
+    }
+}
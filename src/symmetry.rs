@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{bitboard::Bitboard, Cell, CellIndex, TicTacToe};
+
+/// For each of the eight symmetries of the square, the index each cell is mapped to, i.e. the
+/// content of cell `i` moves to cell `SYMMETRIES[s][i]`. Same order as [`Symmetry::ALL`]: Identity,
+/// rotations by 90, 180 and 270 degrees clockwise, reflection left to right, reflection top to
+/// bottom, reflection along the diagonal starting top left and along the one starting top right.
+///
+/// ```
+/// use tic_tac_toe_board::SYMMETRIES;
+///
+/// // Rotating clockwise by 90 degrees moves the top left corner to the top right.
+/// assert_eq!(SYMMETRIES[1][0], 2);
+/// ```
+pub const SYMMETRIES: [[u8; 9]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8],
+    [2, 5, 8, 1, 4, 7, 0, 3, 6],
+    [8, 7, 6, 5, 4, 3, 2, 1, 0],
+    [6, 3, 0, 7, 4, 1, 8, 5, 2],
+    [2, 1, 0, 5, 4, 3, 8, 7, 6],
+    [6, 7, 8, 3, 4, 5, 0, 1, 2],
+    [0, 3, 6, 1, 4, 7, 2, 5, 8],
+    [8, 5, 2, 7, 4, 1, 6, 3, 0],
+];
+
+/// The eight ways to rotate or reflect the board onto itself.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Symmetry {
+    /// Leaves the board unchanged
+    Identity,
+    /// Rotation by 90 degrees clockwise
+    Rotate90,
+    /// Rotation by 180 degrees
+    Rotate180,
+    /// Rotation by 270 degrees clockwise, i.e. 90 degrees counterclockwise
+    Rotate270,
+    /// Swaps the left and right column
+    Mirror,
+    /// Swaps the top and bottom row
+    Flip,
+    /// Reflection along the diagonal starting in the top left corner
+    Transpose,
+    /// Reflection along the diagonal starting in the top right corner
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All symmetries, in the same order as [`SYMMETRIES`].
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::Mirror,
+        Symmetry::Flip,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Where this symmetry maps each cell to, i.e. the corresponding entry of [`SYMMETRIES`].
+    pub fn permutation(self) -> &'static [u8; 9] {
+        &SYMMETRIES[self as usize]
+    }
+
+    /// The cell `index` is mapped to.
+    pub fn apply(self, index: CellIndex) -> CellIndex {
+        CellIndex(self.permutation()[index.0 as usize])
+    }
+}
+
+/// Groups `boards` into clusters of positions which are equal up to rotation and reflection, i.e.
+/// share the same [`TicTacToe::canonical`] form. Clusters are ordered by the ternary id of their
+/// canonical form, boards within a cluster keep the order of the input. Duplicates are kept.
+pub fn group_by_symmetry(boards: &[TicTacToe]) -> Vec<Vec<TicTacToe>> {
+    let mut clusters: BTreeMap<u16, Vec<TicTacToe>> = BTreeMap::new();
+    for &board in boards {
+        clusters
+            .entry(board.canonical().ternary_id())
+            .or_default()
+            .push(board);
+    }
+    clusters.into_values().collect()
+}
+
+impl TicTacToe {
+    /// The board with `symmetry` applied to it.
+    pub fn transform(&self, symmetry: Symmetry) -> TicTacToe {
+        self.permuted(symmetry.permutation())
+    }
+
+    /// The board rotated by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> TicTacToe {
+        self.transform(Symmetry::Rotate90)
+    }
+
+    /// The board rotated by 180 degrees.
+    pub fn rotate_180(&self) -> TicTacToe {
+        self.transform(Symmetry::Rotate180)
+    }
+
+    /// The board rotated by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> TicTacToe {
+        self.transform(Symmetry::Rotate270)
+    }
+
+    /// The board with its left and right column swapped.
+    pub fn mirror(&self) -> TicTacToe {
+        self.transform(Symmetry::Mirror)
+    }
+
+    /// The board reflected along the diagonal starting in the top left corner.
+    pub fn transpose(&self) -> TicTacToe {
+        self.transform(Symmetry::Transpose)
+    }
+
+    /// The board with the stones of player one and player two swapped. Note that the result is
+    /// usually not a legal position, since player one has a stone more than player two after each
+    /// move of player one.
+    pub fn swap_colors(&self) -> TicTacToe {
+        TicTacToe(Bitboard::from_bits(self.0.bits().rotate_left(16)))
+    }
+
+    /// `true` if both boards are equal, either as they are or after swapping the stones of player
+    /// one and player two in one of them. Only the stones are compared, so this ignores whose turn
+    /// it is, which usually differs between a position and its color swap. This is distinct from
+    /// equality up to rotation and reflection, see [`Self::canonical`].
+    pub fn eq_color_blind(&self, other: &TicTacToe) -> bool {
+        self == other || *self == other.swap_colors()
+    }
+
+    /// Representative of all positions which are equal to this one up to rotation and reflection
+    /// of the board. It is the one with the smallest [ternary id](Self::ternary_id), so two
+    /// positions are symmetric to each other if and only if their canonical forms are equal.
+    pub fn canonical(&self) -> TicTacToe {
+        SYMMETRIES
+            .iter()
+            .map(|permutation| self.permuted(permutation))
+            .min_by_key(TicTacToe::ternary_id)
+            .unwrap()
+    }
+
+    /// A symmetry which maps this board onto `other`, i.e. `self.transform(symmetry) == *other`.
+    /// `None` if the boards are not equal up to rotation and reflection. If several symmetries
+    /// work, e.g. because the position is symmetric itself, the first one in the order of
+    /// [`Symmetry::ALL`] is returned, so equal boards always yield [`Symmetry::Identity`].
+    pub fn symmetry_to(&self, other: &TicTacToe) -> Option<Symmetry> {
+        Symmetry::ALL
+            .into_iter()
+            .find(|&symmetry| self.transform(symmetry) == *other)
+    }
+
+    /// Key shared by all positions which are equal to this one up to rotation and reflection of the
+    /// board and swapping the stones of the two players. These are the 16 transformations of
+    /// the group generated by the eight [`SYMMETRIES`] and [`Self::swap_colors`]. The key is the
+    /// smallest [ternary id](Self::ternary_id) among the transformed boards, so two positions are
+    /// equivalent if and only if their keys are equal. Since the color swap ignores whose turn it
+    /// is, equivalent positions may have different players to move.
+    pub fn canonical_key(&self) -> u16 {
+        let swapped = self.swap_colors();
+        SYMMETRIES
+            .iter()
+            .flat_map(|permutation| [self.permuted(permutation), swapped.permuted(permutation)])
+            .map(|board| board.ternary_id())
+            .min()
+            .unwrap()
+    }
+
+    /// Number of positions reachable with a single move, counting positions which are equal up to
+    /// rotation and reflection of the board only once. `3` for the empty board (corner, edge and
+    /// center), `0` once the game is finished.
+    pub fn distinct_successor_count(&self) -> usize {
+        if self.state().is_terminal() {
+            return 0;
+        }
+        self.open_fields()
+            .map(|mov| {
+                let mut child = *self;
+                child.play_move(&mov);
+                child.canonical()
+            })
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// One move out of each group of `moves` which lead to positions equal up to rotation and
+    /// reflection of the board. The lowest move of each group is kept and the result is in
+    /// ascending order. Duplicates and moves into occupied cells are dropped, as is everything once
+    /// the game is finished.
+    pub fn dedup_moves_by_symmetry(&self, moves: &[CellIndex]) -> Vec<CellIndex> {
+        if self.state().is_terminal() {
+            return Vec::new();
+        }
+        let mut moves: Vec<_> = moves
+            .iter()
+            .copied()
+            .filter(|&mov| self.0.field(mov) == Cell::Empty)
+            .collect();
+        moves.sort_by_key(|mov| mov.0);
+        let mut seen = HashSet::new();
+        moves.retain(|mov| {
+            let mut child = *self;
+            child.play_move(mov);
+            seen.insert(child.canonical())
+        });
+        moves
+    }
+
+    /// Moves the content of each cell `i` to cell `permutation[i]`.
+    fn permuted(&self, permutation: &[u8; 9]) -> TicTacToe {
+        let mut board = TicTacToe::new();
+        for (from, &to) in permutation.iter().enumerate() {
+            board
+                .0
+                .mark_cell(CellIndex(to), self.0.field(CellIndex(from as u8)));
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Player;
+
+    use super::*;
+
+    #[test]
+    fn board_transformations() {
+        // Player one in the top left corner, player two right next to it
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(1));
+        let stones = |board: TicTacToe| {
+            (
+                board.player_mask(Player::One).trailing_zeros(),
+                board.player_mask(Player::Two).trailing_zeros(),
+            )
+        };
+
+        assert_eq!(stones(board.rotate_clockwise()), (2, 5));
+        assert_eq!(stones(board.rotate_180()), (8, 7));
+        assert_eq!(stones(board.rotate_counterclockwise()), (6, 3));
+        assert_eq!(stones(board.mirror()), (2, 1));
+        assert_eq!(stones(board.transpose()), (0, 3));
+        assert_eq!(board.rotate_clockwise().rotate_counterclockwise(), board);
+    }
+
+    #[test]
+    fn symmetries_are_permutations() {
+        for symmetry in Symmetry::ALL {
+            let mut seen = [false; 9];
+            for i in (0..9).map(CellIndex::new) {
+                seen[symmetry.apply(i).0 as usize] = true;
+            }
+            assert!(seen.iter().all(|&s| s));
+        }
+    }
+
+    #[test]
+    fn canonical_key_identifies_symmetry_and_color() {
+        let mut corner = TicTacToe::new();
+        corner.play_move(&CellIndex::new(0));
+        let mut opposite = TicTacToe::new();
+        opposite.play_move(&CellIndex::new(4));
+        opposite.play_move(&CellIndex::new(8));
+
+        // Player two alone in a corner swapped to player one alone in a corner
+        let lone_two = corner.swap_colors().rotate_180();
+        assert_eq!(lone_two.canonical_key(), corner.canonical_key());
+        assert_ne!(lone_two.canonical(), corner.canonical());
+        assert_ne!(opposite.canonical_key(), corner.canonical_key());
+        assert_eq!(
+            opposite.canonical_key(),
+            opposite.swap_colors().mirror().canonical_key()
+        );
+    }
+
+    #[test]
+    fn cluster_symmetric_boards() {
+        let corner = TicTacToe::from_opening(CellIndex::new(8));
+        let center = TicTacToe::from_opening(CellIndex::new(4));
+        let other_corner = TicTacToe::from_opening(CellIndex::new(2));
+        let edge = TicTacToe::from_opening(CellIndex::new(1));
+
+        let clusters = group_by_symmetry(&[center, corner, edge, other_corner, center]);
+
+        // Canonical forms are the openings in 0, 1 and 4, in order of their ids
+        assert_eq!(
+            clusters,
+            vec![vec![corner, other_corner], vec![edge], vec![center, center]]
+        );
+    }
+
+    #[test]
+    fn find_symmetry_between_boards() {
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(1));
+
+        assert_eq!(board.symmetry_to(&board), Some(Symmetry::Identity));
+        for symmetry in Symmetry::ALL {
+            let other = board.transform(symmetry);
+            assert_eq!(board.symmetry_to(&other), Some(symmetry));
+        }
+        let mut unrelated = board;
+        unrelated.play_move(&CellIndex::new(4));
+        assert_eq!(board.symmetry_to(&unrelated), None);
+
+        // The center alone is mapped onto itself by every symmetry
+        let mut center = TicTacToe::new();
+        center.play_move(&CellIndex::new(4));
+        assert_eq!(center.symmetry_to(&center), Some(Symmetry::Identity));
+    }
+
+    #[test]
+    fn count_distinct_successors() {
+        assert_eq!(TicTacToe::new().distinct_successor_count(), 3);
+        // Against the center, player two can choose a corner or an edge
+        let center = TicTacToe::from_opening(CellIndex::new(4));
+        assert_eq!(center.distinct_successor_count(), 2);
+        let corner = TicTacToe::from_opening(CellIndex::new(0));
+        assert_eq!(corner.distinct_successor_count(), 5);
+        let won = TicTacToe::from_openings(&[0, 3, 1, 4, 2].map(CellIndex::new)).unwrap();
+        assert_eq!(won.distinct_successor_count(), 0);
+    }
+
+    #[test]
+    fn dedup_symmetric_moves() {
+        let all: Vec<_> = (0..9).rev().map(CellIndex::new).collect();
+        let empty = TicTacToe::new();
+        assert_eq!(
+            empty.dedup_moves_by_symmetry(&all),
+            [0, 1, 4].map(CellIndex::new)
+        );
+
+        // Player one in the corner, player two in the opposite corner. 1 and 3 are related by the
+        // transposition, as are 2 and 6 and 5 and 7.
+        let mut board = TicTacToe::new();
+        board.play_move(&CellIndex::new(0));
+        board.play_move(&CellIndex::new(8));
+        assert_eq!(
+            board.dedup_moves_by_symmetry(&all),
+            [1, 2, 4, 5].map(CellIndex::new)
+        );
+    }
+
+    #[test]
+    fn color_blind_equality() {
+        let mut one = TicTacToe::new();
+        one.play_move(&CellIndex::new(4));
+        one.play_move(&CellIndex::new(0));
+        let mut two = TicTacToe::new();
+        two.play_move(&CellIndex::new(0));
+        two.play_move(&CellIndex::new(4));
+
+        assert_ne!(one, two);
+        assert_eq!(one.swap_colors(), two);
+        assert!(one.eq_color_blind(&two));
+        assert!(one.eq_color_blind(&one));
+        // Symmetric, but not color swapped
+        assert!(!one.eq_color_blind(&one.rotate_clockwise()));
+    }
+
+    #[test]
+    fn corners_share_canonical_form() {
+        let canonical = |i| {
+            let mut board = TicTacToe::new();
+            board.play_move(&CellIndex::new(i));
+            board.canonical()
+        };
+        assert_eq!(canonical(0), canonical(2));
+        assert_eq!(canonical(0), canonical(6));
+        assert_eq!(canonical(0), canonical(8));
+        assert_eq!(canonical(1), canonical(7));
+        assert_ne!(canonical(0), canonical(1));
+        assert_ne!(canonical(0), canonical(4));
+    }
+}
@@ -0,0 +1,94 @@
+//! Precomputed perfect play results for every position.
+
+use std::collections::HashMap;
+
+use crate::{all_legal_positions_canonical, solver::negamax, TicTacToe};
+
+/// Number of entries in the table. One for each ternary id.
+const TABLE_SIZE: usize = 19683;
+
+/// Solves every position and encodes the results in a table, which can be queried with
+/// [`lookup_in`].
+///
+/// The table holds one byte for each [ternary id](TicTacToe::ternary_id), i.e. `19683` bytes in
+/// total. Only the entries of canonical positions (see [`TicTacToe::canonical`]) which can arise in
+/// a legal game are filled in, all others are zero. A position is looked up via the id of its
+/// canonical form. The bits of a filled in entry are:
+///
+/// * Bits 0 and 1: The outcome for the player to move under perfect play. `1` for a win, `2` for
+///   a draw and `3` for a loss. `0` marks an empty entry.
+/// * Bits 2 to 5: Number of plies until the game is finished, if both players play perfectly.
+///   Winners finish as fast as possible, losers delay as long as they can.
+/// * Bits 6 and 7: Always zero.
+///
+/// Note that the table can not be indexed by [`TicTacToe::canonical_key`]. It also identifies
+/// positions whose colors are swapped, yet with the same player to move such positions usually
+/// differ in value.
+pub fn export_tablebase() -> Vec<u8> {
+    let mut table = vec![0; TABLE_SIZE];
+    let mut memo = HashMap::new();
+    for board in all_legal_positions_canonical() {
+        let score = negamax(board, &mut memo);
+        let (outcome, distance) = match score.signum() {
+            1 => (1, 10 - score - i32::from(board.plies_played())),
+            0 => (2, i32::from(board.plies_remaining())),
+            _ => (3, 10 + score - i32::from(board.plies_played())),
+        };
+        table[board.ternary_id() as usize] = outcome | (distance as u8) << 2;
+    }
+    table
+}
+
+/// Looks up `board` in a table created by [`export_tablebase`]. Returns the value of the position
+/// on the scale of [`TicTacToe::evaluate`] and the number of plies until the game is finished under
+/// perfect play. The entry is found at the ternary id of [`TicTacToe::canonical`], see
+/// [`export_tablebase`] for the layout of the table. `None` if the table holds no entry for the
+/// position, e.g. because it is illegal.
+pub fn lookup_in(table: &[u8], board: &TicTacToe) -> Option<(i32, u8)> {
+    let entry = *table.get(board.canonical().ternary_id() as usize)?;
+    let value = match entry & 0b11 {
+        1 => 1,
+        2 => 0,
+        3 => -1,
+        _ => return None,
+    };
+    Some((value, entry >> 2))
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{all_legal_positions, Cell, CellIndex};
+
+    use super::*;
+
+    #[test]
+    fn tablebase_agrees_with_solver() {
+        let table = export_tablebase();
+        assert_eq!(table.len(), TABLE_SIZE);
+        for board in all_legal_positions() {
+            let (value, _) = lookup_in(&table, &board).unwrap();
+            assert_eq!(value, board.evaluate());
+        }
+    }
+
+    #[test]
+    fn distance_to_end_of_game() {
+        let table = export_tablebase();
+        // Perfect play fills the board
+        assert_eq!(lookup_in(&table, &TicTacToe::new()), Some((0, 9)));
+
+        let mut game = TicTacToe::new();
+        for i in [4, 0, 2, 1] {
+            game.play_move(&CellIndex::new(i));
+        }
+        assert_eq!(lookup_in(&table, &game), Some((1, 1)));
+        game.play_move(&CellIndex::new(6));
+        assert_eq!(lookup_in(&table, &game), Some((-1, 0)));
+
+        let mut illegal = TicTacToe::new();
+        illegal.0.mark_cell(CellIndex::new(0), Cell::PlayerTwo);
+        assert_eq!(lookup_in(&table, &illegal), None);
+        assert_eq!(lookup_in(&[], &game), None);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{error::Error, fmt};
+
+use crate::{CellIndex, MoveError, TicTacToe};
+
+/// `true` if `moves` is a legal game, or the beginning of one, starting from the empty board. See
+/// [`validate_transcript`] for details.
+pub fn is_valid_transcript(moves: &[CellIndex]) -> bool {
+    validate_transcript(moves).is_ok()
+}
+
+/// Checks that `moves` is a legal game, or the beginning of one, starting from the empty board.
+/// The players alternate, starting with player one, so each move must be played in an empty cell
+/// and there must not be any moves after one player has won or the board is full. Returns the
+/// position after the last move, or the first illegal move.
+pub fn validate_transcript(moves: &[CellIndex]) -> Result<TicTacToe, TranscriptError> {
+    let mut board = TicTacToe::new();
+    for (ply, &mov) in moves.iter().enumerate() {
+        board
+            .try_play_move(mov)
+            .map_err(|error| TranscriptError { ply, error })?;
+    }
+    Ok(board)
+}
+
+impl TicTacToe {
+    /// The board after player one opened the game in `mov`. Every cell is empty at the start of
+    /// the game, so this can not fail.
+    pub fn from_opening(mov: CellIndex) -> TicTacToe {
+        let mut board = TicTacToe::new();
+        board.play_move(&mov);
+        board
+    }
+
+    /// The board after `moves` have been played from the empty board. Same as
+    /// [`validate_transcript`], i.e. fails at the first illegal move.
+    pub fn from_openings(moves: &[CellIndex]) -> Result<TicTacToe, TranscriptError> {
+        validate_transcript(moves)
+    }
+}
+
+/// Returned by [`validate_transcript`] for the first illegal move of a transcript.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TranscriptError {
+    /// Number of moves played before the illegal one, i.e. the index of the illegal move in the
+    /// transcript.
+    pub ply: usize,
+    /// Why the move is illegal
+    pub error: MoveError,
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Illegal move at ply {}: {}", self.ply, self.error)
+    }
+}
+
+impl Error for TranscriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Player;
+
+    use super::*;
+
+    #[test]
+    fn valid_transcripts() {
+        assert!(is_valid_transcript(&[]));
+        let moves = [4, 0, 2, 6, 3, 5, 1, 7, 8].map(CellIndex::new);
+        let board = validate_transcript(&moves).unwrap();
+        assert_eq!(board.plies_remaining(), 0);
+    }
+
+    #[test]
+    fn set_up_openings() {
+        let corner = TicTacToe::from_opening(CellIndex::new(0));
+        assert_eq!(corner.owner(CellIndex::new(0)), Some(Player::One));
+        assert_eq!(corner.plies_played(), 1);
+
+        let board = TicTacToe::from_openings(&[0, 4].map(CellIndex::new)).unwrap();
+        assert_eq!(board.owner(CellIndex::new(4)), Some(Player::Two));
+        assert!(TicTacToe::from_openings(&[0, 0].map(CellIndex::new)).is_err());
+    }
+
+    #[test]
+    fn first_illegal_move_is_reported() {
+        let occupied = [4, 0, 4, 0].map(CellIndex::new);
+        assert_eq!(
+            validate_transcript(&occupied),
+            Err(TranscriptError {
+                ply: 2,
+                error: MoveError::CellOccupied(CellIndex::new(4))
+            })
+        );
+
+        // Player one completes the top row with the fifth move
+        let after_victory = [0, 3, 1, 4, 2, 5].map(CellIndex::new);
+        assert_eq!(
+            validate_transcript(&after_victory),
+            Err(TranscriptError {
+                ply: 5,
+                error: MoveError::GameFinished
+            })
+        );
+        assert!(!is_valid_transcript(&after_victory));
+    }
+}